@@ -0,0 +1,139 @@
+// src/analytics.rs
+//! Option chain analytics: put-call ratio, max pain and OI buildup.
+use std::cmp::Ordering::{Greater, Less};
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::dates;
+use crate::models::{OptionChain, OptionChainFilter, OptionChainRow, OptionLeg};
+
+/// How open interest and price moved together at one strike
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum OiBuildup {
+    /// Price up, OI up: fresh longs
+    LongBuildup,
+    /// Price down, OI up: fresh shorts
+    ShortBuildup,
+    /// Price up, OI down: shorts exiting
+    ShortCovering,
+    /// Price down, OI down: longs exiting
+    LongUnwinding,
+    /// Price or OI unchanged
+    Neutral,
+}
+
+impl OiBuildup {
+    pub fn classify(price_change: f64, oi_change: f64) -> Self {
+        match (price_change.partial_cmp(&0.0), oi_change.partial_cmp(&0.0)) {
+            (Some(Greater), Some(Greater)) => OiBuildup::LongBuildup,
+            (Some(Less), Some(Greater)) => OiBuildup::ShortBuildup,
+            (Some(Greater), Some(Less)) => OiBuildup::ShortCovering,
+            (Some(Less), Some(Less)) => OiBuildup::LongUnwinding,
+            _ => OiBuildup::Neutral,
+        }
+    }
+
+    fn of_leg(leg: &OptionLeg) -> Self {
+        Self::classify(leg.change.unwrap_or(0.0), leg.change_in_open_interest)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StrikeBuildup {
+    pub strike_price: f64,
+    pub call: Option<OiBuildup>,
+    pub put: Option<OiBuildup>,
+}
+
+/// Metrics for one expiry of an option chain
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpiryAnalytics {
+    #[serde(with = "dates::nse_date")]
+    pub expiry: NaiveDate,
+    pub total_call_oi: f64,
+    pub total_put_oi: f64,
+    pub total_call_volume: u64,
+    pub total_put_volume: u64,
+    /// Put OI / call OI; `None` when there is no call OI
+    pub pcr_oi: Option<f64>,
+    /// Put volume / call volume; `None` when there is no call volume
+    pub pcr_volume: Option<f64>,
+    /// Strike at which option writers pay out the least at expiry
+    pub max_pain: Option<f64>,
+    pub buildup: Vec<StrikeBuildup>,
+}
+
+/// Metrics for every expiry in the chain, nearest first
+pub fn analyze(chain: &OptionChain) -> Vec<ExpiryAnalytics> {
+    let mut expiries = chain.records.expiry_dates.clone();
+    expiries.sort();
+    expiries.dedup();
+
+    expiries
+        .into_iter()
+        .filter_map(|expiry| analyze_expiry(chain, expiry))
+        .collect()
+}
+
+/// Metrics for a single expiry, or `None` if the chain has no rows for it
+pub fn analyze_expiry(chain: &OptionChain, expiry: NaiveDate) -> Option<ExpiryAnalytics> {
+    let rows = chain.rows(&OptionChainFilter::default().expiry(expiry));
+    if rows.is_empty() {
+        return None;
+    }
+
+    let total_call_oi: f64 = rows.iter().filter_map(|r| r.ce.as_ref()).map(|l| l.open_interest).sum();
+    let total_put_oi: f64 = rows.iter().filter_map(|r| r.pe.as_ref()).map(|l| l.open_interest).sum();
+    let total_call_volume: u64 = rows.iter().filter_map(|r| r.ce.as_ref()).map(|l| l.total_traded_volume).sum();
+    let total_put_volume: u64 = rows.iter().filter_map(|r| r.pe.as_ref()).map(|l| l.total_traded_volume).sum();
+
+    let buildup = rows
+        .iter()
+        .map(|row| StrikeBuildup {
+            strike_price: row.strike_price,
+            call: row.ce.as_ref().map(OiBuildup::of_leg),
+            put: row.pe.as_ref().map(OiBuildup::of_leg),
+        })
+        .collect();
+
+    Some(ExpiryAnalytics {
+        expiry,
+        total_call_oi,
+        total_put_oi,
+        total_call_volume,
+        total_put_volume,
+        pcr_oi: ratio(total_put_oi, total_call_oi),
+        pcr_volume: ratio(total_put_volume as f64, total_call_volume as f64),
+        max_pain: max_pain(&rows),
+        buildup,
+    })
+}
+
+/// Strike minimising the total intrinsic value owed to option holders
+pub fn max_pain(rows: &[&OptionChainRow]) -> Option<f64> {
+    let oi = |leg: &Option<OptionLeg>| leg.as_ref().map(|l| l.open_interest).unwrap_or(0.0);
+
+    rows.iter()
+        .map(|settle| {
+            let payout: f64 = rows
+                .iter()
+                .map(|row| {
+                    let call = (settle.strike_price - row.strike_price).max(0.0) * oi(&row.ce);
+                    let put = (row.strike_price - settle.strike_price).max(0.0) * oi(&row.pe);
+                    call + put
+                })
+                .sum();
+            (settle.strike_price, payout)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(strike, _)| strike)
+}
+
+fn ratio(numerator: f64, denominator: f64) -> Option<f64> {
+    if denominator > 0.0 {
+        Some(numerator / denominator)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,597 @@
+// src/bhavcopy.rs
+//! NSE's end-of-day bhavcopy archives.
+//!
+//! A bhavcopy is one zipped CSV per trading day with the OHLC, volume and
+//! turnover of every security, or for F&O every contract with its open
+//! interest. NSE switched both segments from the legacy layouts
+//! (`SYMBOL,SERIES,OPEN,...` and `INSTRUMENT,SYMBOL,EXPIRY_DT,...`) to the
+//! UDiFF layout (`TradDt,BizDt,Sgmt,...`) on [`udiff_start`]; each layout is
+//! parsed into the same [`CmBhavcopyRow`] or [`FoBhavcopyRow`]. Everything
+//! here works on local files as well as on downloads made through
+//! [`NseScraper`](crate::NseScraper).
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::endpoints::Endpoint;
+use crate::error::{NseScraperError, Result};
+use crate::models::{ContractKind, DailyData, HistoricalData};
+use crate::pricing::OptionType;
+use crate::{dates, numbers};
+
+/// Column layout of a bhavcopy CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BhavcopyLayout {
+    Legacy,
+    Udiff,
+}
+
+impl BhavcopyLayout {
+    /// Layout NSE publishes for a trading date
+    pub fn for_date(date: NaiveDate) -> Self {
+        if date >= udiff_start() {
+            BhavcopyLayout::Udiff
+        } else {
+            BhavcopyLayout::Legacy
+        }
+    }
+
+    /// Layout of a CSV whose header row contains `legacy_marker` in the legacy layout
+    fn detect(headers: &csv::StringRecord, legacy_marker: &str) -> Option<Self> {
+        if headers.iter().any(|h| h == "TckrSymb") {
+            Some(BhavcopyLayout::Udiff)
+        } else if headers.iter().any(|h| h == legacy_marker) {
+            Some(BhavcopyLayout::Legacy)
+        } else {
+            None
+        }
+    }
+}
+
+/// First trading day published in the UDiFF layout only
+pub fn udiff_start() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 7, 8).expect("valid date")
+}
+
+/// One security on one day of the cash market bhavcopy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CmBhavcopyRow {
+    pub symbol: String,
+    pub series: String,
+    #[serde(with = "dates::iso_date")]
+    pub date: NaiveDate,
+    pub isin: Option<String>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub last: f64,
+    pub prev_close: f64,
+    pub volume: u64,
+    pub value: f64,
+    pub trades: Option<u64>,
+}
+
+/// One futures or options contract on one day of the F&O bhavcopy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FoBhavcopyRow {
+    pub kind: ContractKind,
+    pub symbol: String,
+    #[serde(with = "dates::iso_date")]
+    pub date: NaiveDate,
+    #[serde(with = "dates::iso_date")]
+    pub expiry_date: NaiveDate,
+    /// `None` for futures
+    pub strike_price: Option<f64>,
+    /// `None` for futures
+    pub option_type: Option<OptionType>,
+    /// `None` when the contract did not trade that day, as are `high`, `low` and `close`
+    pub open: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub close: Option<f64>,
+    /// Set for untraded contracts too, unless NSE left it blank
+    pub settlement_price: Option<f64>,
+    pub contracts: u64,
+    /// Turnover in rupees
+    pub value: f64,
+    /// Open interest in shares (units of the underlying)
+    pub open_interest: f64,
+    pub change_in_open_interest: f64,
+    /// Only published in the UDiFF layout
+    pub underlying_price: Option<f64>,
+}
+
+impl FoBhavcopyRow {
+    pub fn is_future(&self) -> bool {
+        matches!(self.kind, ContractKind::StockFuture | ContractKind::IndexFuture)
+    }
+
+    pub fn is_traded(&self) -> bool {
+        self.contracts > 0
+    }
+
+    /// Close minus the underlying's price; `None` if the contract did not
+    /// trade or the layout does not publish the underlying (legacy)
+    pub fn basis(&self) -> Option<f64> {
+        Some(self.close? - self.underlying_price?)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LegacyCmRecord {
+    #[serde(rename = "SYMBOL")]
+    symbol: String,
+    #[serde(rename = "SERIES")]
+    series: String,
+    #[serde(rename = "OPEN", with = "numbers::required")]
+    open: f64,
+    #[serde(rename = "HIGH", with = "numbers::required")]
+    high: f64,
+    #[serde(rename = "LOW", with = "numbers::required")]
+    low: f64,
+    #[serde(rename = "CLOSE", with = "numbers::required")]
+    close: f64,
+    #[serde(rename = "LAST", with = "numbers::required")]
+    last: f64,
+    #[serde(rename = "PREVCLOSE", with = "numbers::required")]
+    prev_close: f64,
+    #[serde(rename = "TOTTRDQTY", with = "numbers::required")]
+    volume: u64,
+    #[serde(rename = "TOTTRDVAL", with = "numbers::required")]
+    value: f64,
+    #[serde(rename = "TIMESTAMP", with = "dates::nse_date")]
+    date: NaiveDate,
+    #[serde(rename = "TOTALTRADES", default, with = "numbers::option")]
+    trades: Option<u64>,
+    #[serde(rename = "ISIN", default)]
+    isin: Option<String>,
+}
+
+/// A UDiFF cash market row
+#[derive(Debug, Deserialize)]
+struct UdiffRecord {
+    #[serde(rename = "TradDt", with = "dates::nse_date")]
+    date: NaiveDate,
+    #[serde(rename = "TckrSymb")]
+    symbol: String,
+    #[serde(rename = "SctySrs", default)]
+    series: Option<String>,
+    #[serde(rename = "ISIN", default)]
+    isin: Option<String>,
+    #[serde(rename = "OpnPric", with = "numbers::required")]
+    open: f64,
+    #[serde(rename = "HghPric", with = "numbers::required")]
+    high: f64,
+    #[serde(rename = "LwPric", with = "numbers::required")]
+    low: f64,
+    #[serde(rename = "ClsPric", with = "numbers::required")]
+    close: f64,
+    #[serde(rename = "LastPric", default, with = "numbers::option")]
+    last: Option<f64>,
+    #[serde(rename = "PrvsClsgPric", with = "numbers::required")]
+    prev_close: f64,
+    #[serde(rename = "TtlTradgVol", with = "numbers::required")]
+    volume: u64,
+    #[serde(rename = "TtlTrfVal", with = "numbers::required")]
+    value: f64,
+    #[serde(rename = "TtlNbOfTxsExctd", default, with = "numbers::option")]
+    trades: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LegacyFoRecord {
+    #[serde(rename = "INSTRUMENT")]
+    instrument: String,
+    #[serde(rename = "SYMBOL")]
+    symbol: String,
+    #[serde(rename = "EXPIRY_DT", with = "dates::nse_date")]
+    expiry_date: NaiveDate,
+    #[serde(rename = "STRIKE_PR", default, with = "numbers::option")]
+    strike_price: Option<f64>,
+    #[serde(rename = "OPTION_TYP")]
+    option_type: String,
+    #[serde(rename = "OPEN", with = "numbers::required")]
+    open: f64,
+    #[serde(rename = "HIGH", with = "numbers::required")]
+    high: f64,
+    #[serde(rename = "LOW", with = "numbers::required")]
+    low: f64,
+    #[serde(rename = "CLOSE", with = "numbers::required")]
+    close: f64,
+    #[serde(rename = "SETTLE_PR", with = "numbers::required")]
+    settlement_price: f64,
+    #[serde(rename = "CONTRACTS", with = "numbers::required")]
+    contracts: u64,
+    #[serde(rename = "VAL_INLAKH", with = "numbers::required")]
+    value_in_lakh: f64,
+    #[serde(rename = "OPEN_INT", with = "numbers::required")]
+    open_interest: f64,
+    #[serde(rename = "CHG_IN_OI", with = "numbers::required")]
+    change_in_open_interest: f64,
+    #[serde(rename = "TIMESTAMP", with = "dates::nse_date")]
+    date: NaiveDate,
+}
+
+/// A UDiFF F&O row. Untraded contracts leave the price columns blank.
+#[derive(Debug, Deserialize)]
+struct UdiffFoRecord {
+    #[serde(rename = "TradDt", with = "dates::nse_date")]
+    date: NaiveDate,
+    #[serde(rename = "FinInstrmTp")]
+    instrument: String,
+    #[serde(rename = "TckrSymb")]
+    symbol: String,
+    #[serde(rename = "XpryDt", with = "dates::nse_date")]
+    expiry_date: NaiveDate,
+    #[serde(rename = "StrkPric", default, with = "numbers::option")]
+    strike_price: Option<f64>,
+    #[serde(rename = "OptnTp", default)]
+    option_type: Option<String>,
+    #[serde(rename = "OpnPric", default, with = "numbers::option")]
+    open: Option<f64>,
+    #[serde(rename = "HghPric", default, with = "numbers::option")]
+    high: Option<f64>,
+    #[serde(rename = "LwPric", default, with = "numbers::option")]
+    low: Option<f64>,
+    #[serde(rename = "ClsPric", default, with = "numbers::option")]
+    close: Option<f64>,
+    #[serde(rename = "UndrlygPric", default, with = "numbers::option")]
+    underlying_price: Option<f64>,
+    #[serde(rename = "SttlmPric", default, with = "numbers::option")]
+    settlement_price: Option<f64>,
+    #[serde(rename = "OpnIntrst", default, with = "numbers::option")]
+    open_interest: Option<f64>,
+    #[serde(rename = "ChngInOpnIntrst", default, with = "numbers::option")]
+    change_in_open_interest: Option<f64>,
+    /// Quantity traded, in units of the underlying
+    #[serde(rename = "TtlTradgVol", default, with = "numbers::option")]
+    volume: Option<u64>,
+    #[serde(rename = "TtlTrfVal", default, with = "numbers::option")]
+    value: Option<f64>,
+    #[serde(rename = "NewBrdLotQty", default, with = "numbers::option")]
+    lot_size: Option<u64>,
+}
+
+impl From<LegacyCmRecord> for CmBhavcopyRow {
+    fn from(r: LegacyCmRecord) -> Self {
+        CmBhavcopyRow {
+            symbol: r.symbol,
+            series: r.series,
+            date: r.date,
+            isin: r.isin.filter(|isin| !isin.is_empty()),
+            open: r.open,
+            high: r.high,
+            low: r.low,
+            close: r.close,
+            last: r.last,
+            prev_close: r.prev_close,
+            volume: r.volume,
+            value: r.value,
+            trades: r.trades,
+        }
+    }
+}
+
+impl From<UdiffRecord> for CmBhavcopyRow {
+    fn from(r: UdiffRecord) -> Self {
+        CmBhavcopyRow {
+            symbol: r.symbol,
+            series: r.series.unwrap_or_default(),
+            date: r.date,
+            isin: r.isin.filter(|isin| !isin.is_empty()),
+            open: r.open,
+            high: r.high,
+            low: r.low,
+            close: r.close,
+            last: r.last.unwrap_or(r.close),
+            prev_close: r.prev_close,
+            volume: r.volume,
+            value: r.value,
+            trades: r.trades,
+        }
+    }
+}
+
+impl LegacyFoRecord {
+    fn into_row(self) -> Option<FoBhavcopyRow> {
+        let kind = contract_kind(&self.instrument)?;
+        let option_type = parse_option_type(&self.option_type);
+        let traded = self.contracts > 0;
+
+        Some(FoBhavcopyRow {
+            kind,
+            symbol: self.symbol,
+            date: self.date,
+            expiry_date: self.expiry_date,
+            strike_price: self.strike_price.filter(|_| option_type.is_some()),
+            option_type,
+            // The legacy layout writes zero prices for untraded contracts
+            open: traded.then_some(self.open),
+            high: traded.then_some(self.high),
+            low: traded.then_some(self.low),
+            close: traded.then_some(self.close),
+            settlement_price: Some(self.settlement_price),
+            contracts: self.contracts,
+            value: self.value_in_lakh * numbers::RUPEES_PER_LAKH,
+            open_interest: self.open_interest,
+            change_in_open_interest: self.change_in_open_interest,
+            underlying_price: None,
+        })
+    }
+}
+
+impl UdiffFoRecord {
+    fn into_row(self) -> Option<FoBhavcopyRow> {
+        let kind = contract_kind(&self.instrument)?;
+        let option_type = self.option_type.as_deref().and_then(parse_option_type);
+        let volume = self.volume.unwrap_or(0);
+        let contracts = match self.lot_size {
+            Some(lot) if lot > 0 => volume / lot,
+            _ => volume,
+        };
+
+        Some(FoBhavcopyRow {
+            kind,
+            symbol: self.symbol,
+            date: self.date,
+            expiry_date: self.expiry_date,
+            strike_price: self.strike_price.filter(|_| option_type.is_some()),
+            option_type,
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            settlement_price: self.settlement_price,
+            contracts,
+            value: self.value.unwrap_or(0.0),
+            open_interest: self.open_interest.unwrap_or(0.0),
+            change_in_open_interest: self.change_in_open_interest.unwrap_or(0.0),
+            underlying_price: self.underlying_price,
+        })
+    }
+}
+
+/// Instrument codes of both layouts; others (e.g. VIX futures) are skipped
+fn contract_kind(instrument: &str) -> Option<ContractKind> {
+    match instrument {
+        "FUTSTK" | "STF" => Some(ContractKind::StockFuture),
+        "FUTIDX" | "IDF" => Some(ContractKind::IndexFuture),
+        "OPTSTK" | "STO" => Some(ContractKind::StockOption),
+        "OPTIDX" | "IDO" => Some(ContractKind::IndexOption),
+        _ => None,
+    }
+}
+
+/// `CE`/`PE`; futures carry `XX` or a blank
+fn parse_option_type(raw: &str) -> Option<OptionType> {
+    match raw {
+        "CE" => Some(OptionType::Call),
+        "PE" => Some(OptionType::Put),
+        _ => None,
+    }
+}
+
+/// Archive endpoint and file name of the cash market bhavcopy for `date`
+pub fn cm_archive_file(date: NaiveDate) -> (Endpoint, String) {
+    match BhavcopyLayout::for_date(date) {
+        BhavcopyLayout::Legacy => (
+            Endpoint::CmBhavcopy,
+            format!(
+                "{}/cm{}bhav.csv.zip",
+                date.format("%Y/%b").to_string().to_uppercase(),
+                date.format("%d%b%Y").to_string().to_uppercase(),
+            ),
+        ),
+        BhavcopyLayout::Udiff => (
+            Endpoint::CmBhavcopyUdiff,
+            format!("BhavCopy_NSE_CM_0_0_0_{}_F_0000.csv.zip", date.format("%Y%m%d")),
+        ),
+    }
+}
+
+/// Archive endpoint and file name of the F&O bhavcopy for `date`
+pub fn fo_archive_file(date: NaiveDate) -> (Endpoint, String) {
+    match BhavcopyLayout::for_date(date) {
+        BhavcopyLayout::Legacy => (
+            Endpoint::FoBhavcopy,
+            format!(
+                "{}/fo{}bhav.csv.zip",
+                date.format("%Y/%b").to_string().to_uppercase(),
+                date.format("%d%b%Y").to_string().to_uppercase(),
+            ),
+        ),
+        BhavcopyLayout::Udiff => (
+            Endpoint::FoBhavcopyUdiff,
+            format!("BhavCopy_NSE_FO_0_0_0_{}_F_0000.csv.zip", date.format("%Y%m%d")),
+        ),
+    }
+}
+
+/// Parse a cash market bhavcopy CSV in either layout
+pub fn parse_cm_csv<R: Read>(reader: R) -> Result<Vec<CmBhavcopyRow>> {
+    let mut reader = csv_reader(reader);
+    let layout = BhavcopyLayout::detect(reader.headers()?, "SERIES").ok_or_else(|| {
+        NseScraperError::ArchiveError("not a cash market bhavcopy".to_string())
+    })?;
+
+    match layout {
+        BhavcopyLayout::Legacy => read_records::<_, LegacyCmRecord, _>(&mut reader),
+        BhavcopyLayout::Udiff => read_records::<_, UdiffRecord, _>(&mut reader),
+    }
+}
+
+/// Parse a zipped cash market bhavcopy as downloaded from NSE
+pub fn parse_cm_zip<R: Read + Seek>(reader: R) -> Result<Vec<CmBhavcopyRow>> {
+    parse_cm_csv(csv_in_zip(reader)?.as_slice())
+}
+
+/// Parse a cash market bhavcopy from disk, zipped or not
+pub fn read_cm_file(path: impl AsRef<Path>) -> Result<Vec<CmBhavcopyRow>> {
+    let mut file = BufReader::new(File::open(path)?);
+    if is_zip(&mut file)? {
+        parse_cm_zip(file)
+    } else {
+        parse_cm_csv(file)
+    }
+}
+
+/// Parse an F&O bhavcopy CSV in either layout.
+///
+/// Contracts other than stock and index futures and options are skipped.
+pub fn parse_fo_csv<R: Read>(reader: R) -> Result<Vec<FoBhavcopyRow>> {
+    let mut reader = csv_reader(reader);
+    let layout = BhavcopyLayout::detect(reader.headers()?, "INSTRUMENT").ok_or_else(|| {
+        NseScraperError::ArchiveError("not an F&O bhavcopy".to_string())
+    })?;
+
+    let mut rows = Vec::new();
+    match layout {
+        BhavcopyLayout::Legacy => {
+            for record in reader.deserialize::<LegacyFoRecord>() {
+                rows.extend(record?.into_row());
+            }
+        }
+        BhavcopyLayout::Udiff => {
+            for record in reader.deserialize::<UdiffFoRecord>() {
+                rows.extend(record?.into_row());
+            }
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Parse a zipped F&O bhavcopy as downloaded from NSE
+pub fn parse_fo_zip<R: Read + Seek>(reader: R) -> Result<Vec<FoBhavcopyRow>> {
+    parse_fo_csv(csv_in_zip(reader)?.as_slice())
+}
+
+/// Parse an F&O bhavcopy from disk, zipped or not
+pub fn read_fo_file(path: impl AsRef<Path>) -> Result<Vec<FoBhavcopyRow>> {
+    let mut file = BufReader::new(File::open(path)?);
+    if is_zip(&mut file)? {
+        parse_fo_zip(file)
+    } else {
+        parse_fo_csv(file)
+    }
+}
+
+/// Continuous front-month futures series per underlying, sorted by date.
+///
+/// On each day the contract with the nearest expiry at least `roll_days`
+/// calendar days away is chosen, so with `roll_days = 0` the series holds
+/// each contract up to and including its expiry day. The `expiry_date` of
+/// each row shows where the series rolled. Days on which the chosen
+/// contract did not trade stay in the series with `None` prices rather
+/// than jumping to another expiry.
+pub fn front_month_futures(rows: &[FoBhavcopyRow], roll_days: i64) -> BTreeMap<String, Vec<FoBhavcopyRow>> {
+    let mut by_day: BTreeMap<(&str, NaiveDate), &FoBhavcopyRow> = BTreeMap::new();
+    for row in rows.iter().filter(|row| row.is_future()) {
+        if (row.expiry_date - row.date).num_days() < roll_days {
+            continue;
+        }
+        by_day
+            .entry((row.symbol.as_str(), row.date))
+            .and_modify(|front| {
+                if row.expiry_date < front.expiry_date {
+                    *front = row;
+                }
+            })
+            .or_insert(row);
+    }
+
+    let mut series: BTreeMap<String, Vec<FoBhavcopyRow>> = BTreeMap::new();
+    for ((symbol, _), row) in by_day {
+        series.entry(symbol.to_string()).or_default().push(row.clone());
+    }
+    series
+}
+
+/// Group rows of one series into per-symbol histories, sorted by date.
+///
+/// Bhavcopies carry no 52-week range, so `year_high`/`year_low` are filled
+/// in by [`HistoricalData::fill_year_range`] from the rows given.
+pub fn to_historical(rows: &[CmBhavcopyRow], series: &str) -> BTreeMap<String, HistoricalData> {
+    let mut by_symbol: BTreeMap<String, Vec<&CmBhavcopyRow>> = BTreeMap::new();
+    for row in rows.iter().filter(|row| row.series.eq_ignore_ascii_case(series)) {
+        by_symbol.entry(row.symbol.clone()).or_default().push(row);
+    }
+
+    by_symbol
+        .into_iter()
+        .map(|(symbol, mut rows)| {
+            rows.sort_by_key(|row| row.date);
+            rows.dedup_by_key(|row| row.date);
+
+            let data = rows
+                .iter()
+                .map(|row| DailyData {
+                    timestamp: row.date,
+                    open: row.open,
+                    high: row.high,
+                    low: row.low,
+                    close: row.close,
+                    last: row.last,
+                    prev_close: row.prev_close,
+                    volume: row.volume,
+                    value: row.value,
+                    ..Default::default()
+                })
+                .collect();
+
+            let mut history = HistoricalData {
+                symbol: symbol.clone(),
+                data,
+            };
+            history.fill_year_range();
+            (symbol, history)
+        })
+        .collect()
+}
+
+pub(crate) fn csv_reader<R: Read>(reader: R) -> csv::Reader<R> {
+    csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(reader)
+}
+
+/// Deserialize every record of `reader` and convert it to `T`
+pub(crate) fn read_records<R, Raw, T>(reader: &mut csv::Reader<R>) -> Result<Vec<T>>
+where
+    R: Read,
+    Raw: DeserializeOwned + Into<T>,
+{
+    reader
+        .deserialize::<Raw>()
+        .map(|record| Ok(record?.into()))
+        .collect()
+}
+
+/// Contents of the first CSV file in a zip archive
+pub(crate) fn csv_in_zip<R: Read + Seek>(reader: R) -> Result<Vec<u8>> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let name = archive
+        .file_names()
+        .find(|name| name.to_ascii_lowercase().ends_with(".csv"))
+        .map(str::to_string)
+        .ok_or_else(|| NseScraperError::ArchiveError("zip contains no CSV file".to_string()))?;
+
+    let mut contents = Vec::new();
+    archive.by_name(&name)?.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+/// Whether a file starts with the zip signature; leaves the reader at the start
+pub(crate) fn is_zip<R: Read + Seek>(reader: &mut R) -> Result<bool> {
+    let mut magic = [0u8; 4];
+    let read = reader.read(&mut magic)?;
+    reader.rewind()?;
+    Ok(read == 4 && magic == *b"PK\x03\x04")
+}
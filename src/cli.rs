@@ -1,5 +1,5 @@
 // src/cli.rs
-use crate::{NseScraper, Result};
+use crate::{NseScraper, Result, Symbol};
 use chrono::{Local, NaiveDate}; // Removed Datelike
 use csv::Writer;
 use std::fs::File;
@@ -7,6 +7,9 @@ use std::io::{self, Write};
 use std::path::Path;
 use crate::models::TimeFrame;
 
+// Dates are shown and written as DD-MM-YYYY
+const DATE_FORMAT: &str = "%d-%m-%Y";
+
 pub struct NseCli {
     scraper: NseScraper,
 }
@@ -22,7 +25,7 @@ impl NseCli {
         println!("====================");
 
         // Get stock symbol
-        let symbol = Self::prompt_input("Enter stock symbol (e.g., RELIANCE): ")?;
+        let symbol = Symbol::new(&Self::prompt_input("Enter stock symbol (e.g., RELIANCE): ")?)?;
         
         // Get time frame
         println!("\nSelect time frame:");
@@ -46,10 +49,15 @@ impl NseCli {
         // Calculate date range
         let (from_date, to_date) = Self::calculate_date_range(&duration_choice)?;
         
-        println!("\nFetching data for {} from {} to {}...", symbol, from_date, to_date);
+        println!(
+            "\nFetching data for {} from {} to {}...",
+            symbol,
+            from_date.format(DATE_FORMAT),
+            to_date.format(DATE_FORMAT)
+        );
         
         // Get historical data
-        let historical = self.scraper.get_historical_data(&symbol, "EQ", &from_date, &to_date).await?;
+        let historical = self.scraper.get_historical_range(&symbol, "EQ", from_date, to_date).await?;
         
         // Convert to selected time frame
         let consolidated = historical.to_time_frame(time_frame_enum);
@@ -59,8 +67,8 @@ impl NseCli {
             "{}_{}_{}_{}.csv",
             symbol,
             consolidated.time_frame,
-            consolidated.from_date,
-            consolidated.to_date
+            consolidated.from_date.unwrap_or(from_date).format(DATE_FORMAT),
+            consolidated.to_date.unwrap_or(to_date).format(DATE_FORMAT)
         );
         
         // Save to CSV
@@ -81,9 +89,8 @@ impl NseCli {
         Ok(input.trim().to_string())
     }
     
-    fn calculate_date_range(choice: &str) -> std::io::Result<(String, String)> {
+    fn calculate_date_range(choice: &str) -> std::io::Result<(NaiveDate, NaiveDate)> {
         let today = Local::now().naive_local().date();
-        let to_date = today.format("%d-%m-%Y").to_string();
         
         let from_date = match choice.trim() {
             "1" => today.checked_sub_signed(chrono::Duration::days(7)),
@@ -120,7 +127,7 @@ impl NseCli {
         };
         
         match from_date {
-            Some(date) => Ok((date.format("%d-%m-%Y").to_string(), to_date)),
+            Some(date) => Ok((date, today)),
             None => Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid date calculation")),
         }
     }
@@ -130,12 +137,12 @@ impl NseCli {
         let mut writer = Writer::from_writer(file);
         
         // Write header
-        writer.write_record(&["Date", "Open", "High", "Low", "Close", "Volume", "Value"])?;
+        writer.write_record(["Date", "Open", "High", "Low", "Close", "Volume", "Value"])?;
         
         // Write data
         for day in data {
-            writer.write_record(&[
-                &day.date,
+            writer.write_record([
+                &day.date.format(DATE_FORMAT).to_string(),
                 &day.open.to_string(),
                 &day.high.to_string(),
                 &day.low.to_string(),
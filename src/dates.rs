@@ -0,0 +1,300 @@
+// src/dates.rs
+//! Parsing and serde helpers for the date formats NSE uses.
+//!
+//! NSE is inconsistent: quotes carry `15-Mar-2024`, the historical API
+//! `2024-03-15` or `15-03-2024`, and timestamps `15-Mar-2024 15:30:00` in
+//! India Standard Time. The parsers here accept all of them; the serde
+//! modules write back the format the corresponding NSE field uses.
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Weekday};
+
+/// India Standard Time offset (UTC+05:30)
+pub const IST_OFFSET_SECS: i32 = 5 * 3600 + 30 * 60;
+
+const DATE_FORMATS: [&str; 6] = [
+    "%d-%b-%Y",
+    "%d-%m-%Y",
+    "%Y-%m-%d",
+    "%d-%B-%Y",
+    "%d %b %Y",
+    "%d/%m/%Y",
+];
+
+const DATETIME_FORMATS: [&str; 6] = [
+    "%d-%b-%Y %H:%M:%S",
+    "%d-%b-%Y %H:%M",
+    "%d-%m-%Y %H:%M:%S",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%d-%b-%Y %H:%M:%S%.f",
+];
+
+const NSE_DATE_FORMAT: &str = "%d-%b-%Y";
+const ISO_DATE_FORMAT: &str = "%Y-%m-%d";
+const NSE_DATETIME_FORMAT: &str = "%d-%b-%Y %H:%M:%S";
+
+pub fn ist() -> FixedOffset {
+    FixedOffset::east_opt(IST_OFFSET_SECS).expect("IST offset is in range")
+}
+
+/// Parse any of NSE's date formats. Timestamps are reduced to their IST date.
+pub fn parse_nse_date(s: &str) -> Option<NaiveDate> {
+    let s = s.trim();
+
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(s, format).ok())
+        .or_else(|| parse_nse_datetime(s).map(|dt| dt.date_naive()))
+}
+
+/// Parse any of NSE's timestamp formats. Timestamps without an offset are taken to be IST.
+pub fn parse_nse_datetime(s: &str) -> Option<DateTime<FixedOffset>> {
+    let s = s.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&ist()));
+    }
+
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .and_then(|naive| ist().from_local_datetime(&naive).single())
+}
+
+/// Monday-to-Friday dates in `from..=to`. Exchange holidays are not excluded.
+pub fn weekdays(from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    from.iter_days()
+        .take_while(|day| *day <= to)
+        .filter(|day| !matches!(day.weekday(), Weekday::Sat | Weekday::Sun))
+        .collect()
+}
+
+/// Whether NSE left a date field empty
+fn is_blank(raw: &str) -> bool {
+    let raw = raw.trim();
+    raw.is_empty() || raw == "-"
+}
+
+/// Dates written as `15-Mar-2024`
+pub mod nse_date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&date.format(super::NSE_DATE_FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        super::parse_nse_date(&raw)
+            .ok_or_else(|| serde::de::Error::custom(format!("unrecognised date: {}", raw)))
+    }
+}
+
+/// Optional dates written as `15-Mar-2024`; blanks and `-` read as `None`
+pub mod option_nse_date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(date: &Option<NaiveDate>, serializer: S) -> Result<S::Ok, S::Error> {
+        match date {
+            Some(date) => super::nse_date::serialize(date, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NaiveDate>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(raw) if !super::is_blank(&raw) => super::parse_nse_date(&raw)
+                .map(Some)
+                .ok_or_else(|| serde::de::Error::custom(format!("unrecognised date: {}", raw))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Dates written as `2024-03-15`
+pub mod iso_date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&date.format(super::ISO_DATE_FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        super::parse_nse_date(&raw)
+            .ok_or_else(|| serde::de::Error::custom(format!("unrecognised date: {}", raw)))
+    }
+}
+
+/// Optional dates written as `2024-03-15`; blanks and `-` read as `None`
+pub mod option_iso_date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(date: &Option<NaiveDate>, serializer: S) -> Result<S::Ok, S::Error> {
+        match date {
+            Some(date) => super::iso_date::serialize(date, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NaiveDate>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(raw) if !super::is_blank(&raw) => super::parse_nse_date(&raw)
+                .map(Some)
+                .ok_or_else(|| serde::de::Error::custom(format!("unrecognised date: {}", raw))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// IST timestamps written as `15-Mar-2024 15:30:00`
+pub mod ist_datetime {
+    use chrono::{DateTime, FixedOffset};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(dt: &DateTime<FixedOffset>, serializer: S) -> Result<S::Ok, S::Error> {
+        let ist = dt.with_timezone(&super::ist());
+        serializer.serialize_str(&ist.format(super::NSE_DATETIME_FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<FixedOffset>, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        super::parse_nse_datetime(&raw)
+            .ok_or_else(|| serde::de::Error::custom(format!("unrecognised timestamp: {}", raw)))
+    }
+}
+
+/// Optional IST timestamps; blanks and `-` read as `None`
+pub mod option_ist_datetime {
+    use chrono::{DateTime, FixedOffset};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(dt: &Option<DateTime<FixedOffset>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match dt {
+            Some(dt) => super::ist_datetime::serialize(dt, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<FixedOffset>>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(raw) if !super::is_blank(&raw) => super::parse_nse_datetime(&raw)
+                .map(Some)
+                .ok_or_else(|| serde::de::Error::custom(format!("unrecognised timestamp: {}", raw))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Lists of dates written as `15-Mar-2024`
+pub mod nse_date_seq {
+    use chrono::NaiveDate;
+    use serde::ser::SerializeSeq;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(dates: &[NaiveDate], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(dates.len()))?;
+        for date in dates {
+            seq.serialize_element(&date.format(super::NSE_DATE_FORMAT).to_string())?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<NaiveDate>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|raw| {
+                super::parse_nse_date(raw)
+                    .ok_or_else(|| serde::de::Error::custom(format!("unrecognised date: {}", raw)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+    use serde::Deserialize;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_every_date_format() {
+        for raw in ["15-Mar-2024", "15-03-2024", "2024-03-15", "15-March-2024", "15 Mar 2024", "15/03/2024", " 15-MAR-2024 "] {
+            assert_eq!(parse_nse_date(raw), Some(date(2024, 3, 15)), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_dates() {
+        for raw in ["", "-", "31-Feb-2024", "2024/03/15", "15-Foo-2024", "yesterday"] {
+            assert_eq!(parse_nse_date(raw), None, "{}", raw);
+        }
+    }
+
+    #[test]
+    fn reads_naive_timestamps_as_ist() {
+        let dt = parse_nse_datetime("15-Mar-2024 15:30:00").unwrap();
+        assert_eq!(dt.offset().local_minus_utc(), IST_OFFSET_SECS);
+        assert_eq!((dt.hour(), dt.minute()), (15, 30));
+        assert_eq!(dt.naive_utc().hour(), 10);
+
+        assert_eq!(parse_nse_datetime("15-Mar-2024 15:30"), Some(dt));
+        assert_eq!(parse_nse_datetime("2024-03-15 15:30:00"), Some(dt));
+        assert_eq!(parse_nse_date("15-Mar-2024 15:30:00"), Some(date(2024, 3, 15)));
+    }
+
+    #[test]
+    fn converts_offset_timestamps_to_ist() {
+        // 20:00 UTC is 01:30 the next day in India
+        let dt = parse_nse_datetime("2024-03-15T20:00:00Z").unwrap();
+        assert_eq!(dt.offset().local_minus_utc(), IST_OFFSET_SECS);
+        assert_eq!((dt.hour(), dt.minute()), (1, 30));
+        assert_eq!(parse_nse_date("2024-03-15T20:00:00Z"), Some(date(2024, 3, 16)));
+    }
+
+    #[test]
+    fn weekdays_skip_weekends() {
+        let days = weekdays(date(2024, 3, 15), date(2024, 3, 19));
+        assert_eq!(days, [date(2024, 3, 15), date(2024, 3, 18), date(2024, 3, 19)]);
+        assert!(weekdays(date(2024, 3, 16), date(2024, 3, 17)).is_empty());
+    }
+
+    #[derive(Deserialize)]
+    struct Row {
+        #[serde(with = "nse_date")]
+        date: NaiveDate,
+        #[serde(with = "option_nse_date")]
+        expiry: Option<NaiveDate>,
+        #[serde(with = "option_ist_datetime")]
+        updated: Option<DateTime<FixedOffset>>,
+    }
+
+    #[test]
+    fn serde_helpers_treat_blanks_as_none_and_reject_garbage() {
+        let row: Row = serde_json::from_str(r#"{"date": "15-03-2024", "expiry": "-", "updated": ""}"#).unwrap();
+        assert_eq!(row.date, date(2024, 3, 15));
+        assert_eq!(row.expiry, None);
+        assert_eq!(row.updated, None);
+
+        let row: Row = serde_json::from_str(r#"{"date": "2024-03-15", "expiry": null, "updated": "15-Mar-2024 09:15:00"}"#).unwrap();
+        assert_eq!(row.expiry, None);
+        assert_eq!(row.updated.unwrap().hour(), 9);
+
+        assert!(serde_json::from_str::<Row>(r#"{"date": "15-Xyz-2024", "expiry": null, "updated": null}"#).is_err());
+        assert!(serde_json::from_str::<Row>(r#"{"date": "15-Mar-2024", "expiry": "soon", "updated": null}"#).is_err());
+    }
+
+    #[test]
+    fn serializes_in_ist() {
+        let utc = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+        let json = ist_datetime::serialize(&utc, serde_json::value::Serializer).unwrap();
+        assert_eq!(json, "15-Mar-2024 15:30:00");
+    }
+}
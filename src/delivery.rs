@@ -0,0 +1,163 @@
+// src/delivery.rs
+//! Security-wise delivery positions.
+//!
+//! NSE publishes how much of each day's traded quantity was settled by
+//! delivery in two archives: the full security bhavdata
+//! (`sec_bhavdata_full_DDMMYYYY.csv`, with `DELIV_QTY`/`DELIV_PER` columns)
+//! and the older MTO file (`MTO_DDMMYYYY.DAT`). Both parse into
+//! [`DeliveryRow`], which can be merged into [`HistoricalData`].
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::bhavcopy::{csv_reader, read_records};
+use crate::endpoints::Endpoint;
+use crate::error::{NseScraperError, Result};
+use crate::models::HistoricalData;
+use crate::{dates, numbers};
+
+/// Delivery position of one security on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryRow {
+    pub symbol: String,
+    pub series: String,
+    #[serde(with = "dates::iso_date")]
+    pub date: NaiveDate,
+    pub traded_qty: u64,
+    /// `None` for series that are not settled by delivery
+    pub delivery_qty: Option<u64>,
+    pub delivery_pct: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BhavdataRecord {
+    #[serde(rename = "SYMBOL")]
+    symbol: String,
+    #[serde(rename = "SERIES")]
+    series: String,
+    #[serde(rename = "DATE1", with = "dates::nse_date")]
+    date: NaiveDate,
+    #[serde(rename = "TTL_TRD_QNTY", with = "numbers::required")]
+    traded_qty: u64,
+    #[serde(rename = "DELIV_QTY", default, with = "numbers::option")]
+    delivery_qty: Option<u64>,
+    #[serde(rename = "DELIV_PER", default, with = "numbers::option")]
+    delivery_pct: Option<f64>,
+}
+
+impl From<BhavdataRecord> for DeliveryRow {
+    fn from(r: BhavdataRecord) -> Self {
+        DeliveryRow {
+            symbol: r.symbol,
+            series: r.series,
+            date: r.date,
+            traded_qty: r.traded_qty,
+            delivery_qty: r.delivery_qty,
+            delivery_pct: r.delivery_pct,
+        }
+    }
+}
+
+/// Archive endpoint and file name of the full security bhavdata for `date`
+pub fn bhavdata_archive_file(date: NaiveDate) -> (Endpoint, String) {
+    (Endpoint::SecurityBhavdata, format!("sec_bhavdata_full_{}.csv", date.format("%d%m%Y")))
+}
+
+/// Archive endpoint and file name of the MTO delivery file for `date`
+pub fn mto_archive_file(date: NaiveDate) -> (Endpoint, String) {
+    (Endpoint::DeliveryMto, format!("MTO_{}.DAT", date.format("%d%m%Y")))
+}
+
+/// Parse a `sec_bhavdata_full` CSV
+pub fn parse_bhavdata_csv<R: Read>(reader: R) -> Result<Vec<DeliveryRow>> {
+    let mut reader = csv_reader(reader);
+    if !reader.headers()?.iter().any(|h| h == "DELIV_QTY") {
+        return Err(NseScraperError::ArchiveError("not a security-wise bhavdata file".to_string()));
+    }
+    read_records::<_, BhavdataRecord, _>(&mut reader)
+}
+
+/// Parse an MTO file.
+///
+/// The trade date comes from the `Trade Date <04-JUL-2024>` header line;
+/// delivery records are the lines of record type `20`.
+pub fn parse_mto<R: Read>(mut reader: R) -> Result<Vec<DeliveryRow>> {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents)?;
+
+    let date = contents
+        .lines()
+        .find_map(|line| {
+            let (_, rest) = line.split_once("Trade Date <")?;
+            let (raw, _) = rest.split_once('>')?;
+            dates::parse_nse_date(raw)
+        })
+        .ok_or_else(|| NseScraperError::ArchiveError("MTO file has no trade date".to_string()))?;
+
+    let mut rows = Vec::new();
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        // 20,<sr no>,<symbol>,<series>,<traded>,<deliverable>,<% deliverable>
+        let [record_type, _, symbol, series, traded, delivered, pct, ..] = fields[..] else {
+            continue;
+        };
+        if record_type != "20" {
+            continue;
+        }
+
+        let traded_qty = traded.parse().map_err(|_| {
+            NseScraperError::ArchiveError(format!("invalid traded quantity for {}: {}", symbol, traded))
+        })?;
+        rows.push(DeliveryRow {
+            symbol: symbol.to_string(),
+            series: series.to_string(),
+            date,
+            traded_qty,
+            delivery_qty: delivered.parse().ok(),
+            delivery_pct: pct.parse().ok(),
+        });
+    }
+
+    Ok(rows)
+}
+
+/// Parse either delivery file from disk
+pub fn read_delivery_file(path: impl AsRef<Path>) -> Result<Vec<DeliveryRow>> {
+    let mut contents = Vec::new();
+    File::open(path)?.read_to_end(&mut contents)?;
+
+    let first_line = contents.split(|b| *b == b'\n').next().unwrap_or_default();
+    if String::from_utf8_lossy(first_line).contains("SYMBOL") {
+        parse_bhavdata_csv(contents.as_slice())
+    } else {
+        parse_mto(contents.as_slice())
+    }
+}
+
+/// Rows of one series keyed by symbol and date
+pub fn by_symbol_and_date<'a>(rows: &'a [DeliveryRow], series: &str) -> HashMap<(&'a str, NaiveDate), &'a DeliveryRow> {
+    rows.iter()
+        .filter(|row| row.series.eq_ignore_ascii_case(series))
+        .map(|row| ((row.symbol.as_str(), row.date), row))
+        .collect()
+}
+
+impl HistoricalData {
+    /// Fill `delivery_qty`/`delivery_pct` from delivery rows of `series`.
+    ///
+    /// Days without a matching row keep whatever they already had.
+    pub fn merge_delivery(&mut self, rows: &[DeliveryRow], series: &str) {
+        let index = by_symbol_and_date(rows, series);
+
+        for day in &mut self.data {
+            if let Some(row) = index.get(&(self.symbol.as_str(), day.timestamp)) {
+                day.delivery_qty = row.delivery_qty;
+                day.delivery_pct = row.delivery_pct;
+            }
+        }
+    }
+}
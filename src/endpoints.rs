@@ -0,0 +1,174 @@
+// src/endpoints.rs
+use std::collections::HashMap;
+
+/// Default NSE host used when no base URL is configured
+pub const DEFAULT_BASE_URL: &str = "https://www.nseindia.com";
+
+/// Default host of NSE's downloadable end-of-day archives
+pub const DEFAULT_ARCHIVE_URL: &str = "https://nsearchives.nseindia.com";
+
+/// NSE resources the scraper knows how to call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// Home page, visited to obtain session cookies
+    Home,
+    Quote,
+    QuoteDerivative,
+    MarketStatus,
+    PreOpen,
+    AllIndices,
+    /// Top gainers or losers, selected by the `index` parameter
+    Variations,
+    /// Most active securities, by the `index` parameter (volume or value)
+    MostActive,
+    YearHighs,
+    YearLows,
+    PriceBandHitters,
+    /// Latest day's FII/DII cash market activity
+    FiiDiiActivity,
+    HistoricalEquity,
+    OptionChainIndices,
+    OptionChainEquities,
+    CorporateActions,
+    Announcements,
+    BoardMeetings,
+    FinancialResults,
+    InsiderTrading,
+    SastDisclosures,
+    /// Today's bulk, block and short selling deals
+    LargeDeals,
+    BulkDeals,
+    BlockDeals,
+    ShortSelling,
+    IndexHistory,
+    IndexStocks,
+    VixHistory,
+    /// Directory of legacy-format cash market bhavcopies
+    CmBhavcopy,
+    /// Directory of UDiFF-format cash market bhavcopies
+    CmBhavcopyUdiff,
+    /// Directory of legacy-format F&O bhavcopies
+    FoBhavcopy,
+    /// Directory of UDiFF-format F&O bhavcopies
+    FoBhavcopyUdiff,
+    /// Directory of full security bhavdata, including delivery positions
+    SecurityBhavdata,
+    /// Directory of MTO security-wise delivery files
+    DeliveryMto,
+}
+
+impl Endpoint {
+    /// Path of the endpoint relative to the base URL
+    pub fn default_path(&self) -> &'static str {
+        match self {
+            Endpoint::Home => "/",
+            Endpoint::Quote => "/api/quote-equity",
+            Endpoint::QuoteDerivative => "/api/quote-derivative",
+            Endpoint::MarketStatus => "/api/marketStatus",
+            Endpoint::PreOpen => "/api/market-data-pre-open",
+            Endpoint::AllIndices => "/api/allIndices",
+            Endpoint::Variations => "/api/live-analysis-variations",
+            Endpoint::MostActive => "/api/live-analysis-most-active-securities",
+            Endpoint::YearHighs => "/api/live-analysis-data-52weekhighstock",
+            Endpoint::YearLows => "/api/live-analysis-data-52weeklowstock",
+            Endpoint::PriceBandHitters => "/api/live-analysis-price-band-hitter",
+            Endpoint::FiiDiiActivity => "/api/fiidiiTradeReact",
+            Endpoint::HistoricalEquity => "/api/historical/cm/equity",
+            Endpoint::OptionChainIndices => "/api/option-chain-indices",
+            Endpoint::OptionChainEquities => "/api/option-chain-equities",
+            Endpoint::CorporateActions => "/api/corporates-corporateActions",
+            Endpoint::Announcements => "/api/corporate-announcements",
+            Endpoint::BoardMeetings => "/api/corporate-board-meetings",
+            Endpoint::FinancialResults => "/api/corporates-financial-results",
+            Endpoint::InsiderTrading => "/api/corporates-pit",
+            Endpoint::SastDisclosures => "/api/corporate-sast-reg29",
+            Endpoint::LargeDeals => "/api/snapshot-capital-market-largedeal",
+            Endpoint::BulkDeals => "/api/historical/bulk-deals",
+            Endpoint::BlockDeals => "/api/historical/block-deals",
+            Endpoint::ShortSelling => "/api/historical/short-selling",
+            Endpoint::IndexHistory => "/api/historical/indicesHistory",
+            Endpoint::IndexStocks => "/api/equity-stockIndices",
+            Endpoint::VixHistory => "/api/historical/vixhistory",
+            Endpoint::CmBhavcopy => "/content/historical/EQUITIES",
+            Endpoint::CmBhavcopyUdiff => "/content/cm",
+            Endpoint::FoBhavcopy => "/content/historical/DERIVATIVES",
+            Endpoint::FoBhavcopyUdiff => "/content/fo",
+            Endpoint::SecurityBhavdata => "/products/content",
+            Endpoint::DeliveryMto => "/archives/equities/mto",
+        }
+    }
+
+    /// Whether the endpoint is served from the archive host rather than the base URL
+    pub fn is_archive(&self) -> bool {
+        matches!(
+            self,
+            Endpoint::CmBhavcopy
+                | Endpoint::CmBhavcopyUdiff
+                | Endpoint::FoBhavcopy
+                | Endpoint::FoBhavcopyUdiff
+                | Endpoint::SecurityBhavdata
+                | Endpoint::DeliveryMto
+        )
+    }
+}
+
+/// Resolves endpoints to absolute URLs.
+///
+/// API endpoints live under the base URL and archive endpoints under the
+/// archive URL, unless an override has been registered for the endpoint,
+/// in which case the override is used verbatim.
+#[derive(Debug, Clone)]
+pub struct EndpointTable {
+    base_url: String,
+    archive_url: String,
+    overrides: HashMap<Endpoint, String>,
+}
+
+impl EndpointTable {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            archive_url: DEFAULT_ARCHIVE_URL.to_string(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn set_base_url(&mut self, base_url: &str) {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn set_archive_url(&mut self, archive_url: &str) {
+        self.archive_url = archive_url.trim_end_matches('/').to_string();
+    }
+
+    pub fn archive_url(&self) -> &str {
+        &self.archive_url
+    }
+
+    pub fn set_override(&mut self, endpoint: Endpoint, url: &str) {
+        self.overrides.insert(endpoint, url.to_string());
+    }
+
+    pub fn url(&self, endpoint: Endpoint) -> String {
+        match self.overrides.get(&endpoint) {
+            Some(url) => url.clone(),
+            None if endpoint.is_archive() => format!("{}{}", self.archive_url, endpoint.default_path()),
+            None => format!("{}{}", self.base_url, endpoint.default_path()),
+        }
+    }
+
+    /// URL of a file below a directory-style endpoint such as [`Endpoint::CmBhavcopy`]
+    pub fn file_url(&self, endpoint: Endpoint, file: &str) -> String {
+        format!("{}/{}", self.url(endpoint).trim_end_matches('/'), file.trim_start_matches('/'))
+    }
+}
+
+impl Default for EndpointTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASE_URL)
+    }
+}
@@ -0,0 +1,132 @@
+// src/error.rs
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::endpoints::Endpoint;
+
+/// Longest slice of a response body kept in [`NseScraperError::Decode`]
+const BODY_EXCERPT_LEN: usize = 200;
+
+#[derive(Error, Debug)]
+pub enum NseScraperError {
+    #[error("HTTP request failed: {0}")]
+    RequestError(#[from] reqwest::Error),
+
+    #[error("Failed to decode {endpoint:?} response: {source} (body: {body_excerpt})")]
+    Decode {
+        endpoint: Endpoint,
+        body_excerpt: String,
+        source: serde_json::Error,
+    },
+
+    #[error("Invalid symbol: {0}")]
+    InvalidSymbol(String),
+
+    #[error("NSE rejected the session (status {status})")]
+    Unauthorized { status: u16 },
+
+    #[error("Rate limited by NSE (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+
+    #[error("Not found{}", .symbol.as_ref().map(|s| format!(": {}", s)).unwrap_or_default())]
+    NotFound { symbol: Option<String> },
+
+    #[error("NSE server error (status {status})")]
+    ServerError { status: u16 },
+
+    #[error("Unexpected response status {status}")]
+    UnexpectedStatus { status: u16 },
+
+    #[error("{what} missing from the {endpoint:?} response")]
+    MissingData { endpoint: Endpoint, what: &'static str },
+
+    /// A retryable failure that persisted through every attempt the
+    /// [`RetryPolicy`](crate::RetryPolicy) allowed. `source` is the last
+    /// failure; use [`root`](Self::root) or [`is_rate_limited`](Self::is_rate_limited)
+    /// to inspect it without matching on this variant.
+    #[error("Giving up after {attempts} attempts: {source}")]
+    RetriesExhausted {
+        attempts: u32,
+        source: Box<NseScraperError>,
+    },
+
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+
+    /// NSE does not publish the requested data
+    #[error("Not available from NSE: {0}")]
+    Unsupported(&'static str),
+
+    #[error("Background task failed: {0}")]
+    TaskError(#[from] tokio::task::JoinError),
+
+    #[error("Failed to read CSV: {0}")]
+    CsvError(#[from] csv::Error),
+
+    #[error("Failed to read zip archive: {0}")]
+    ZipError(#[from] zip::result::ZipError),
+
+    #[error("Unrecognised archive: {0}")]
+    ArchiveError(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl NseScraperError {
+    /// Whether repeating the same request may succeed.
+    ///
+    /// True for throttling, 5xx responses and connection-level failures.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            NseScraperError::RateLimited { .. } | NseScraperError::ServerError { .. } => true,
+            NseScraperError::RequestError(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+            _ => false,
+        }
+    }
+
+    /// The underlying failure, looking through [`RetriesExhausted`](Self::RetriesExhausted)
+    pub fn root(&self) -> &NseScraperError {
+        match self {
+            NseScraperError::RetriesExhausted { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// Whether NSE throttled the request, whether or not it was retried
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self.root(), NseScraperError::RateLimited { .. })
+    }
+
+    /// Map a non-success HTTP status to an error
+    pub(crate) fn from_status(status: StatusCode, retry_after: Option<Duration>, symbol: Option<&str>) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => NseScraperError::Unauthorized {
+                status: status.as_u16(),
+            },
+            StatusCode::TOO_MANY_REQUESTS => NseScraperError::RateLimited { retry_after },
+            StatusCode::NOT_FOUND => NseScraperError::NotFound {
+                symbol: symbol.map(str::to_string),
+            },
+            s if s.is_server_error() => NseScraperError::ServerError { status: s.as_u16() },
+            s => NseScraperError::UnexpectedStatus { status: s.as_u16() },
+        }
+    }
+
+    pub(crate) fn decode(endpoint: Endpoint, body: &str, source: serde_json::Error) -> Self {
+        let body_excerpt = match body.char_indices().nth(BODY_EXCERPT_LEN) {
+            Some((end, _)) => format!("{}...", &body[..end]),
+            None => body.to_string(),
+        };
+
+        NseScraperError::Decode {
+            endpoint,
+            body_excerpt,
+            source,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, NseScraperError>;
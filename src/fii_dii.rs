@@ -0,0 +1,83 @@
+// src/fii_dii.rs
+//! FII/FPI and DII trading activity in the cash market.
+//!
+//! NSE's `fiidiiTradeReact` endpoint only serves the latest trading day and
+//! NSE keeps no archive of earlier days. Values are in ₹ crores throughout.
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{dates, numbers};
+
+/// Buy, sell and net value of one investor category on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiiDiiActivity {
+    /// As published, e.g. "FII/FPI *" or "DII **"
+    #[serde(rename = "category")]
+    pub category: String,
+    #[serde(rename = "date", with = "dates::nse_date")]
+    pub date: NaiveDate,
+    #[serde(rename = "buyValue", with = "numbers::required")]
+    pub buy_value: f64,
+    #[serde(rename = "sellValue", with = "numbers::required")]
+    pub sell_value: f64,
+    #[serde(rename = "netValue", with = "numbers::required")]
+    pub net_value: f64,
+}
+
+/// Investor categories of the FII/DII report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvestorCategory {
+    /// Foreign institutional and portfolio investors
+    Fii,
+    /// Domestic institutional investors
+    Dii,
+    Other,
+}
+
+/// FII and DII flows of one day, for plotting against index history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiiDiiDay {
+    #[serde(with = "dates::iso_date")]
+    pub date: NaiveDate,
+    pub fii: Option<FiiDiiActivity>,
+    pub dii: Option<FiiDiiActivity>,
+}
+
+impl InvestorCategory {
+    pub fn from_text(text: &str) -> InvestorCategory {
+        let text = text.to_ascii_uppercase();
+        if text.contains("FII") || text.contains("FPI") {
+            InvestorCategory::Fii
+        } else if text.contains("DII") {
+            InvestorCategory::Dii
+        } else {
+            InvestorCategory::Other
+        }
+    }
+}
+
+impl FiiDiiActivity {
+    pub fn investor_category(&self) -> InvestorCategory {
+        InvestorCategory::from_text(&self.category)
+    }
+}
+
+/// FII and DII rows paired by date, in date order
+pub fn by_date(rows: &[FiiDiiActivity]) -> Vec<FiiDiiDay> {
+    let mut days: BTreeMap<NaiveDate, FiiDiiDay> = BTreeMap::new();
+    for row in rows {
+        let day = days.entry(row.date).or_insert_with(|| FiiDiiDay {
+            date: row.date,
+            fii: None,
+            dii: None,
+        });
+        match row.investor_category() {
+            InvestorCategory::Fii => day.fii = Some(row.clone()),
+            InvestorCategory::Dii => day.dii = Some(row.clone()),
+            InvestorCategory::Other => {}
+        }
+    }
+    days.into_values().collect()
+}
@@ -1,122 +1,939 @@
 // src/lib.rs
-use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
-use thiserror::Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, REFERER, USER_AGENT};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
 
 pub mod models;
+pub mod analytics;
+pub mod bhavcopy;
 pub mod cli;
+pub mod dates;
+pub mod delivery;
+pub mod endpoints;
+pub mod error;
+pub mod fii_dii;
+pub mod numbers;
+pub mod pricing;
+pub mod rate_limit;
+pub mod retry;
+pub mod stats;
+pub mod symbol;
+
+pub use endpoints::{Endpoint, EndpointTable};
+pub use error::{NseScraperError, Result};
+pub use rate_limit::RateLimit;
+pub use retry::RetryPolicy;
+pub use stats::RequestStats;
+pub use symbol::Symbol;
+
+use rate_limit::RateLimiter;
+use stats::StatsRecorder;
+
+/// Longest span, in days, NSE's historical equity endpoint serves in one request
+pub const HISTORICAL_WINDOW_DAYS: i64 = 365;
 
-#[derive(Error, Debug)]
-pub enum NseScraperError {
-    #[error("HTTP request failed: {0}")]
-    RequestError(#[from] reqwest::Error),
-    
-    #[error("Failed to parse response: {0}")]
-    ParseError(#[from] serde_json::Error),
-    
-    #[error("Invalid symbol: {0}")]
-    InvalidSymbol(String),
-    
-    #[error("API error: {0}")]
-    ApiError(String),
-    
-    #[error("IO error: {0}")]
-    IoError(#[from] std::io::Error),
+/// Most daily archive downloads a date range keeps in flight at once
+const MAX_ARCHIVE_DOWNLOADS: usize = 8;
+
+const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36";
+
+/// Builder for [`NseScraper`].
+///
+/// ```no_run
+/// # fn main() -> nse_scraper::Result<()> {
+/// use std::time::Duration;
+/// use nse_scraper::NseScraper;
+///
+/// let scraper = NseScraper::builder()
+///     .base_url("http://127.0.0.1:8080")
+///     .timeout(Duration::from_secs(10))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct NseScraperBuilder {
+    endpoints: EndpointTable,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    headers: HeaderMap,
+    proxy: Option<reqwest::Proxy>,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<RateLimit>,
 }
 
-pub type Result<T> = std::result::Result<T, NseScraperError>;
+impl NseScraperBuilder {
+    pub fn new() -> Self {
+        Self {
+            endpoints: EndpointTable::default(),
+            timeout: None,
+            connect_timeout: None,
+            headers: HeaderMap::new(),
+            proxy: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limit: Some(RateLimit::default()),
+        }
+    }
+
+    /// Host every endpoint is resolved against, e.g. a mock server or caching proxy
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.endpoints.set_base_url(base_url);
+        self
+    }
+
+    /// Host the end-of-day archives (bhavcopies) are downloaded from
+    pub fn archive_url(mut self, archive_url: &str) -> Self {
+        self.endpoints.set_archive_url(archive_url);
+        self
+    }
+
+    /// Use an absolute URL for a single endpoint instead of deriving it from the base URL
+    pub fn endpoint(mut self, endpoint: Endpoint, url: &str) -> Self {
+        self.endpoints.set_override(endpoint, url);
+        self
+    }
+
+    /// Total timeout applied to every request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for establishing the TCP/TLS connection
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Extra header sent with every request. Overrides the built-in defaults.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// How transient failures are retried. Use [`RetryPolicy::none`] to disable retries.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Request budget shared by every clone of the built scraper
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = Some(limit);
+        self
+    }
+
+    /// Send requests as fast as they are made
+    pub fn without_rate_limit(mut self) -> Self {
+        self.rate_limit = None;
+        self
+    }
+
+    pub fn build(self) -> Result<NseScraper> {
+        let base_url = self.endpoints.base_url();
+        reqwest::Url::parse(base_url).map_err(|e| {
+            NseScraperError::ConfigError(format!("invalid base URL {}: {}", base_url, e))
+        })?;
 
+        let archive_url = self.endpoints.archive_url();
+        reqwest::Url::parse(archive_url).map_err(|e| {
+            NseScraperError::ConfigError(format!("invalid archive URL {}: {}", archive_url, e))
+        })?;
+
+        let referer = HeaderValue::from_str(&format!("{}/", base_url)).map_err(|e| {
+            NseScraperError::ConfigError(format!("invalid base URL {}: {}", base_url, e))
+        })?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+        headers.insert(ACCEPT, HeaderValue::from_static("*/*"));
+        headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.9"));
+        headers.insert(REFERER, referer);
+        for (name, value) in &self.headers {
+            headers.insert(name.clone(), value.clone());
+        }
+
+        let mut client = reqwest::Client::builder()
+            .default_headers(headers)
+            .cookie_store(true);
+
+        if let Some(timeout) = self.timeout {
+            client = client.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+        if let Some(proxy) = self.proxy {
+            client = client.proxy(proxy);
+        }
+
+        Ok(NseScraper {
+            client: client.build()?,
+            endpoints: Arc::new(self.endpoints),
+            session: Arc::new(Mutex::new(0)),
+            retry_policy: self.retry_policy,
+            rate_limiter: Arc::new(RateLimiter::new(self.rate_limit)),
+            stats: Arc::new(StatsRecorder::default()),
+        })
+    }
+}
+
+impl Default for NseScraperBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Client for the NSE website.
+///
+/// Cloning is cheap; clones share the session cookies, rate limiter and
+/// request statistics.
+#[derive(Clone)]
 pub struct NseScraper {
     client: reqwest::Client,
+    endpoints: Arc<EndpointTable>,
+    // Generation of the NSE session held by the cookie jar; 0 until the first priming
+    session: Arc<Mutex<u64>>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Arc<RateLimiter>,
+    stats: Arc<StatsRecorder>,
 }
 
 impl NseScraper {
+    /// Create a scraper talking to www.nseindia.com with default settings
     pub fn new() -> Result<Self> {
-        let mut headers = HeaderMap::new();
-        headers.insert(USER_AGENT, HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36"));
-        
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .build()?;
-            
-        Ok(Self { client })
+        NseScraperBuilder::new().build()
+    }
+
+    pub fn builder() -> NseScraperBuilder {
+        NseScraperBuilder::new()
     }
-    
+
+    /// Cumulative request counters: calls, attempts and time spent throttled
+    pub fn stats(&self) -> RequestStats {
+        self.stats.snapshot()
+    }
+
     /// Get quote data for a specific symbol
-    pub async fn get_quote(&self, symbol: &str) -> Result<models::QuoteData> {
-        let url = format!("https://www.nseindia.com/api/quote-equity?symbol={}", symbol);
-        
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
-            
-        if !response.status().is_success() {
-            return Err(NseScraperError::ApiError(format!(
-                "API returned error status: {}", response.status()
-            )));
+    pub async fn get_quote(&self, symbol: &Symbol) -> Result<models::QuoteData> {
+        self.get_symbol_json(Endpoint::Quote, symbol.as_str(), &[("symbol", symbol.as_str())]).await
+    }
+
+    /// Get futures and options contracts on a stock or index
+    pub async fn get_derivative_quote(&self, symbol: &Symbol) -> Result<models::DerivativeQuote> {
+        self.get_symbol_json(Endpoint::QuoteDerivative, symbol.as_str(), &[("symbol", symbol.as_str())]).await
+    }
+
+    /// Get corporate announcements, newest first as NSE lists them
+    pub async fn get_announcements(&self, filter: &models::FilingFilter) -> Result<Vec<models::Announcement>> {
+        let query = filing_query(filter, &[]);
+        self.get_json(Endpoint::Announcements, &query_refs(&query)).await
+    }
+
+    /// Get board meetings, past and scheduled
+    pub async fn get_board_meetings(&self, filter: &models::FilingFilter) -> Result<Vec<models::BoardMeeting>> {
+        let query = filing_query(filter, &[]);
+        self.get_json(Endpoint::BoardMeetings, &query_refs(&query)).await
+    }
+
+    /// Get filed financial results for one reporting period type
+    pub async fn get_financial_results(&self, filter: &models::FilingFilter, period: models::ResultPeriod) -> Result<Vec<models::FinancialResult>> {
+        let query = filing_query(filter, &[("period", period.as_param())]);
+        self.get_json(Endpoint::FinancialResults, &query_refs(&query)).await
+    }
+
+    /// Get insider trading (PIT) disclosures
+    pub async fn get_insider_trades(&self, filter: &models::FilingFilter) -> Result<Vec<models::InsiderTrade>> {
+        let query = filing_query(filter, &[]);
+        let trades: models::InsiderTrades = self.get_json(Endpoint::InsiderTrading, &query_refs(&query)).await?;
+        Ok(trades.data)
+    }
+
+    /// Get substantial acquisition (SAST regulation 29) disclosures
+    pub async fn get_sast_disclosures(&self, filter: &models::FilingFilter) -> Result<Vec<models::SastDisclosure>> {
+        let query = filing_query(filter, &[]);
+        let disclosures: models::SastDisclosures = self.get_json(Endpoint::SastDisclosures, &query_refs(&query)).await?;
+        Ok(disclosures.data)
+    }
+
+    /// Get the announcements broadcast since `cursor` last saw one, oldest first,
+    /// and move the cursor past them.
+    ///
+    /// When `filter` has no dates, the request covers the cursor's last day up to today.
+    pub async fn get_new_announcements(&self, cursor: &mut models::FilingCursor, filter: &models::FilingFilter) -> Result<Vec<models::Announcement>> {
+        let mut filter = filter.clone();
+        if filter.from_date.is_none() {
+            if let Some(last_seen) = cursor.last_seen() {
+                let today = chrono::Utc::now().with_timezone(&dates::ist()).date_naive();
+                filter = filter.date_range(last_seen.date_naive(), today);
+            }
         }
-        
-        let data = response.json::<models::QuoteData>().await?;
-        Ok(data)
+        let announcements = self.get_announcements(&filter).await?;
+        Ok(cursor.advance(announcements))
     }
-    
+
     /// Get market status
     pub async fn get_market_status(&self) -> Result<models::MarketStatus> {
-        let url = "https://www.nseindia.com/api/marketStatus";
-        
-        let response = self.client
-            .get(url)
-            .send()
-            .await?;
-            
-        if !response.status().is_success() {
-            return Err(NseScraperError::ApiError(format!(
-                "API returned error status: {}", response.status()
-            )));
-        }
-        
-        let data = response.json::<models::MarketStatus>().await?;
-        Ok(data)
+        self.get_json(Endpoint::MarketStatus, &[]).await
+    }
+
+    /// Get the pre-open session (09:00-09:08 IST) book of a group of stocks
+    pub async fn get_pre_open(&self, segment: models::PreOpenSegment) -> Result<models::PreOpenMarket> {
+        self.get_json(Endpoint::PreOpen, &[("key", segment.as_key())]).await
     }
-    
+
     /// Get indices data
     pub async fn get_indices(&self) -> Result<models::IndicesData> {
-        let url = "https://www.nseindia.com/api/allIndices";
-        
-        let response = self.client
-            .get(url)
-            .send()
+        self.get_json(Endpoint::AllIndices, &[]).await
+    }
+
+    /// Get the top gainers of an index
+    pub async fn get_top_gainers(&self, index: models::MoversIndex) -> Result<Vec<models::MoverRow>> {
+        let variations: models::Variations = self.get_json(Endpoint::Variations, &[("index", "gainers")]).await?;
+        Ok(variations.rows(index))
+    }
+
+    /// Get the top losers of an index
+    pub async fn get_top_losers(&self, index: models::MoversIndex) -> Result<Vec<models::MoverRow>> {
+        // NSE spells the parameter this way
+        let variations: models::Variations = self.get_json(Endpoint::Variations, &[("index", "loosers")]).await?;
+        Ok(variations.rows(index))
+    }
+
+    /// Get the most active securities by traded volume
+    pub async fn get_most_active_by_volume(&self) -> Result<Vec<models::MoverRow>> {
+        let active: models::MostActive = self.get_json(Endpoint::MostActive, &[("index", "volume")]).await?;
+        Ok(active.rows())
+    }
+
+    /// Get the most active securities by traded value
+    pub async fn get_most_active_by_value(&self) -> Result<Vec<models::MoverRow>> {
+        let active: models::MostActive = self.get_json(Endpoint::MostActive, &[("index", "value")]).await?;
+        Ok(active.rows())
+    }
+
+    /// Get the stocks that made a new 52-week high today
+    pub async fn get_year_highs(&self) -> Result<Vec<models::MoverRow>> {
+        let extremes: models::YearExtremes = self.get_json(Endpoint::YearHighs, &[]).await?;
+        Ok(extremes.highs())
+    }
+
+    /// Get the stocks that made a new 52-week low today
+    pub async fn get_year_lows(&self) -> Result<Vec<models::MoverRow>> {
+        let extremes: models::YearExtremes = self.get_json(Endpoint::YearLows, &[]).await?;
+        Ok(extremes.lows())
+    }
+
+    /// Get the stocks locked at their upper and lower price bands, in that order
+    pub async fn get_circuit_hitters(&self) -> Result<(Vec<models::MoverRow>, Vec<models::MoverRow>)> {
+        let hitters: models::PriceBandHitters = self.get_json(Endpoint::PriceBandHitters, &[]).await?;
+        Ok((hitters.upper.rows(), hitters.lower.rows()))
+    }
+
+    /// Get every market movers list at once, gainers and losers for `index`.
+    ///
+    /// The requests run concurrently, subject to the client's rate limit.
+    pub async fn get_market_movers(&self, index: models::MoversIndex) -> Result<models::MarketMovers> {
+        let (gainers, losers, by_volume, by_value, year_highs, year_lows, (upper_circuit, lower_circuit)) = tokio::try_join!(
+            self.get_top_gainers(index),
+            self.get_top_losers(index),
+            self.get_most_active_by_volume(),
+            self.get_most_active_by_value(),
+            self.get_year_highs(),
+            self.get_year_lows(),
+            self.get_circuit_hitters(),
+        )?;
+
+        Ok(models::MarketMovers {
+            gainers,
+            losers,
+            most_active_by_volume: by_volume,
+            most_active_by_value: by_value,
+            year_highs,
+            year_lows,
+            upper_circuit,
+            lower_circuit,
+        })
+    }
+
+    /// Get the latest day's FII/FPI and DII buy, sell and net values, in ₹ crores
+    pub async fn get_fii_dii_activity(&self) -> Result<Vec<fii_dii::FiiDiiActivity>> {
+        self.get_json(Endpoint::FiiDiiActivity, &[]).await
+    }
+
+    /// Earlier days' FII/DII activity.
+    ///
+    /// NSE publishes no archive of these figures, so this always fails with
+    /// [`NseScraperError::Unsupported`] without making a request. Store the
+    /// rows of [`get_fii_dii_activity`](Self::get_fii_dii_activity) each day
+    /// to build a history.
+    pub async fn get_fii_dii_history(&self, _from_date: NaiveDate, _to_date: NaiveDate) -> Result<Vec<fii_dii::FiiDiiActivity>> {
+        Err(NseScraperError::Unsupported("historical FII/DII activity"))
+    }
+
+    /// Get historical data for a symbol
+    pub async fn get_historical_data(&self, symbol: &Symbol, series: &str, from_date: NaiveDate, to_date: NaiveDate) -> Result<models::HistoricalData> {
+        let from = from_date.format("%d-%m-%Y").to_string();
+        let to = to_date.format("%d-%m-%Y").to_string();
+        let query = [
+            ("symbol", symbol.as_str()),
+            ("series", series),
+            ("from", from.as_str()),
+            ("to", to.as_str()),
+        ];
+        self.get_symbol_json(Endpoint::HistoricalEquity, symbol.as_str(), &query).await
+    }
+
+    /// Get historical data for a span of any length.
+    ///
+    /// The span is split into windows of at most [`HISTORICAL_WINDOW_DAYS`]
+    /// that are fetched concurrently, subject to the client's rate limit.
+    /// The result is sorted by date with duplicate days removed.
+    pub async fn get_historical_range(&self, symbol: &Symbol, series: &str, from_date: NaiveDate, to_date: NaiveDate) -> Result<models::HistoricalData> {
+        let windows = self
+            .get_windowed(from_date, to_date, |scraper, from, to| {
+                let symbol = symbol.clone();
+                let series = series.to_string();
+                async move { scraper.get_historical_data(&symbol, &series, from, to).await }
+            })
             .await?;
-            
-        if !response.status().is_success() {
-            return Err(NseScraperError::ApiError(format!(
-                "API returned error status: {}", response.status()
-            )));
+
+        let mut data: Vec<models::DailyData> = windows
+            .into_iter()
+            .flat_map(|window| window.data)
+            .collect();
+        data.sort_by_key(|d| d.timestamp);
+        data.dedup_by_key(|d| d.timestamp);
+
+        Ok(models::HistoricalData {
+            symbol: symbol.to_string(),
+            data,
+        })
+    }
+
+    /// Get daily OHLC of an index such as "NIFTY 50" or "NIFTY BANK".
+    ///
+    /// Spans longer than [`HISTORICAL_WINDOW_DAYS`] are fetched in
+    /// concurrent windows like [`get_historical_range`](Self::get_historical_range).
+    /// The rows have the same shape as an equity's, so time frame
+    /// conversion and indicators work on them unchanged.
+    pub async fn get_index_history(&self, index: &str, from_date: NaiveDate, to_date: NaiveDate) -> Result<models::HistoricalData> {
+        let index = index_name(index)?;
+        let windows = self
+            .get_windowed(from_date, to_date, |scraper, from, to| {
+                let index = index.clone();
+                async move {
+                    let from = from.format("%d-%m-%Y").to_string();
+                    let to = to.format("%d-%m-%Y").to_string();
+                    let query = [("indexType", index.as_str()), ("from", from.as_str()), ("to", to.as_str())];
+                    scraper.get_symbol_json::<models::IndexHistory>(Endpoint::IndexHistory, &index, &query).await
+                }
+            })
+            .await?;
+
+        let mut windows = windows.into_iter();
+        let Some(mut history) = windows.next() else {
+            return Ok(models::HistoricalData {
+                symbol: index,
+                data: Vec::new(),
+            });
+        };
+        for window in windows {
+            history.data.close_records.extend(window.data.close_records);
+            history.data.turnover_records.extend(window.data.turnover_records);
         }
-        
-        let data = response.json::<models::IndicesData>().await?;
-        Ok(data)
+
+        Ok(history.into_historical(&index))
     }
-    
-    /// Get historical data for a symbol
-    pub async fn get_historical_data(&self, symbol: &str, series: &str, from_date: &str, to_date: &str) -> Result<models::HistoricalData> {
-        let url = format!(
-            "https://www.nseindia.com/api/historical/cm/equity?symbol={}&series={}&from={}&to={}",
-            symbol, series, from_date, to_date
-        );
-        
+
+    /// Get the current India VIX level
+    pub async fn get_india_vix(&self) -> Result<models::IndiaVix> {
+        let indices = self.get_indices().await?;
+        models::IndiaVix::from_indices(&indices)
+            .ok_or(NseScraperError::MissingData {
+                endpoint: Endpoint::AllIndices,
+                what: "INDIA VIX",
+            })
+    }
+
+    /// Get daily India VIX OHLC, in the same shape as [`get_index_history`](Self::get_index_history)
+    pub async fn get_vix_history(&self, from_date: NaiveDate, to_date: NaiveDate) -> Result<models::HistoricalData> {
+        let windows = self
+            .get_windowed(from_date, to_date, |scraper, from, to| async move {
+                let from = from.format("%d-%m-%Y").to_string();
+                let to = to.format("%d-%m-%Y").to_string();
+                let query = [("from", from.as_str()), ("to", to.as_str())];
+                scraper.get_json::<models::VixHistory>(Endpoint::VixHistory, &query).await
+            })
+            .await?;
+
+        let history = models::VixHistory {
+            data: windows.into_iter().flat_map(|window| window.data).collect(),
+        };
+        Ok(history.into_historical())
+    }
+
+    /// Get the members of an index with their free-float market cap and weight
+    pub async fn get_index_constituents(&self, index: &str) -> Result<Vec<models::IndexConstituent>> {
+        let index = index_name(index)?;
+        let stocks: models::IndexStocks = self
+            .get_symbol_json(Endpoint::IndexStocks, &index, &[("index", index.as_str())])
+            .await?;
+        Ok(stocks.constituents())
+    }
+
+    /// Split `from_date..=to_date` into [`HISTORICAL_WINDOW_DAYS`] windows
+    /// and run `fetch` on them concurrently, returning the results in window order
+    async fn get_windowed<T, F, Fut>(&self, from_date: NaiveDate, to_date: NaiveDate, fetch: F) -> Result<Vec<T>>
+    where
+        T: Send + 'static,
+        F: Fn(NseScraper, NaiveDate, NaiveDate) -> Fut,
+        Fut: std::future::Future<Output = Result<T>> + Send + 'static,
+    {
+        let mut tasks = tokio::task::JoinSet::new();
+        for (index, (from, to)) in date_windows(from_date, to_date, HISTORICAL_WINDOW_DAYS).into_iter().enumerate() {
+            let window = fetch(self.clone(), from, to);
+            tasks.spawn(async move { (index, window.await) });
+        }
+
+        let mut windows = Vec::with_capacity(tasks.len());
+        while let Some(joined) = tasks.join_next().await {
+            let (index, data) = joined?;
+            windows.push((index, data?));
+        }
+        windows.sort_by_key(|(index, _)| *index);
+
+        Ok(windows.into_iter().map(|(_, data)| data).collect())
+    }
+
+    /// Get the corporate actions of a symbol with an ex-date between `from_date` and `to_date`
+    pub async fn get_corporate_actions(&self, symbol: &Symbol, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<models::CorporateAction>> {
+        let from = from_date.format("%d-%m-%Y").to_string();
+        let to = to_date.format("%d-%m-%Y").to_string();
+        let query = [
+            ("index", "equities"),
+            ("symbol", symbol.as_str()),
+            ("from_date", from.as_str()),
+            ("to_date", to.as_str()),
+        ];
+        // No actions in the period is an empty list, not an unknown symbol
+        self.get_json(Endpoint::CorporateActions, &query).await
+    }
+
+    /// Get historical data back-adjusted for the symbol's corporate actions.
+    ///
+    /// Splits, bonuses and rights issues are always applied; dividends only
+    /// when `include_dividends` is set.
+    pub async fn get_adjusted_historical(&self, symbol: &Symbol, series: &str, from_date: NaiveDate, to_date: NaiveDate, include_dividends: bool) -> Result<models::HistoricalData> {
+        let mut history = self.get_historical_range(symbol, series, from_date, to_date).await?;
+        // Actions up to today still affect every row in the requested span
+        let today = chrono::Utc::now().with_timezone(&dates::ist()).date_naive();
+        let actions = self.get_corporate_actions(symbol, from_date, today.max(to_date)).await?;
+        history.adjust_for_corporate_actions(&actions, include_dividends);
+        Ok(history)
+    }
+
+    /// Get today's bulk deals, block deals and short selling
+    pub async fn get_large_deals(&self) -> Result<models::LargeDeals> {
+        self.get_json(Endpoint::LargeDeals, &[]).await
+    }
+
+    /// Get bulk deals between two dates, optionally for one symbol, sorted by date
+    pub async fn get_bulk_deals(&self, symbol: Option<&Symbol>, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<models::Deal>> {
+        self.get_deal_history(Endpoint::BulkDeals, symbol, from_date, to_date).await
+    }
+
+    /// Get block deals between two dates, optionally for one symbol, sorted by date
+    pub async fn get_block_deals(&self, symbol: Option<&Symbol>, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<models::Deal>> {
+        self.get_deal_history(Endpoint::BlockDeals, symbol, from_date, to_date).await
+    }
+
+    /// Get reported short selling between two dates, optionally for one symbol, sorted by date
+    pub async fn get_short_selling(&self, symbol: Option<&Symbol>, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<models::ShortSale>> {
+        let symbol = symbol.map(Symbol::to_string);
+        let windows = self
+            .get_windowed(from_date, to_date, |scraper, from, to| {
+                let symbol = symbol.clone();
+                async move {
+                    let query = deal_query(symbol.as_deref(), from, to);
+                    scraper.get_json::<models::ShortSellingRecords>(Endpoint::ShortSelling, &query_refs(&query)).await
+                }
+            })
+            .await?;
+
+        let mut sales: Vec<models::ShortSale> = windows.into_iter().flat_map(|window| window.data).collect();
+        sales.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.symbol.cmp(&b.symbol)));
+        Ok(sales)
+    }
+
+    /// Fetch a historical bulk or block deal endpoint in [`HISTORICAL_WINDOW_DAYS`] windows
+    async fn get_deal_history(&self, endpoint: Endpoint, symbol: Option<&Symbol>, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<models::Deal>> {
+        let symbol = symbol.map(Symbol::to_string);
+        let windows = self
+            .get_windowed(from_date, to_date, |scraper, from, to| {
+                let symbol = symbol.clone();
+                async move {
+                    let query = deal_query(symbol.as_deref(), from, to);
+                    scraper.get_json::<models::DealRecords>(endpoint, &query_refs(&query)).await
+                }
+            })
+            .await?;
+
+        let mut deals: Vec<models::Deal> = windows.into_iter().flat_map(|window| window.data).collect();
+        deals.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.symbol.cmp(&b.symbol)));
+        Ok(deals)
+    }
+
+    /// Download and parse the cash market bhavcopy for one trading day.
+    ///
+    /// NSE answers 404 for days without a bhavcopy, which surfaces as
+    /// [`NseScraperError::NotFound`].
+    pub async fn get_cm_bhavcopy(&self, date: NaiveDate) -> Result<Vec<bhavcopy::CmBhavcopyRow>> {
+        let (endpoint, file) = bhavcopy::cm_archive_file(date);
+        let body = self.fetch_bytes(&self.endpoints.file_url(endpoint, &file), &[], None).await?;
+        bhavcopy::parse_cm_zip(std::io::Cursor::new(body))
+    }
+
+    /// Cash market bhavcopies for every trading day in `from_date..=to_date`.
+    ///
+    /// Weekends are skipped without a request and holidays are recognised by
+    /// NSE's 404; a range in which every weekday 404s fails with
+    /// [`NseScraperError::NotFound`]. Up to eight days are fetched
+    /// concurrently, subject to the client's rate limit; rows are ordered by
+    /// date, then symbol.
+    pub async fn get_cm_bhavcopy_range(&self, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<bhavcopy::CmBhavcopyRow>> {
+        let mut rows = self
+            .get_daily_archives(from_date, to_date, |scraper, date| async move {
+                scraper.get_cm_bhavcopy(date).await
+            })
+            .await?;
+        rows.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.symbol.cmp(&b.symbol)));
+
+        Ok(rows)
+    }
+
+    /// Download and parse the F&O bhavcopy for one trading day, all expiries
+    pub async fn get_fo_bhavcopy(&self, date: NaiveDate) -> Result<Vec<bhavcopy::FoBhavcopyRow>> {
+        let (endpoint, file) = bhavcopy::fo_archive_file(date);
+        let body = self.fetch_bytes(&self.endpoints.file_url(endpoint, &file), &[], None).await?;
+        bhavcopy::parse_fo_zip(std::io::Cursor::new(body))
+    }
+
+    /// F&O bhavcopies for every trading day in `from_date..=to_date`, ordered by date.
+    ///
+    /// Weekends and holidays are skipped as in [`get_cm_bhavcopy_range`](Self::get_cm_bhavcopy_range).
+    pub async fn get_fo_bhavcopy_range(&self, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<bhavcopy::FoBhavcopyRow>> {
+        self.get_daily_archives(from_date, to_date, |scraper, date| async move {
+            scraper.get_fo_bhavcopy(date).await
+        })
+        .await
+    }
+
+    /// Security-wise delivery positions for one trading day, from the full security bhavdata
+    pub async fn get_delivery(&self, date: NaiveDate) -> Result<Vec<delivery::DeliveryRow>> {
+        let (endpoint, file) = delivery::bhavdata_archive_file(date);
+        let body = self.fetch_bytes(&self.endpoints.file_url(endpoint, &file), &[], None).await?;
+        delivery::parse_bhavdata_csv(body.as_slice())
+    }
+
+    /// Security-wise delivery positions for one trading day, from the MTO file
+    pub async fn get_delivery_mto(&self, date: NaiveDate) -> Result<Vec<delivery::DeliveryRow>> {
+        let (endpoint, file) = delivery::mto_archive_file(date);
+        let body = self.fetch_bytes(&self.endpoints.file_url(endpoint, &file), &[], None).await?;
+        delivery::parse_mto(body.as_slice())
+    }
+
+    /// Delivery positions for every trading day in `from_date..=to_date`, ordered by date.
+    ///
+    /// Weekends and holidays are skipped as in [`get_cm_bhavcopy_range`](Self::get_cm_bhavcopy_range).
+    pub async fn get_delivery_range(&self, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<delivery::DeliveryRow>> {
+        self.get_daily_archives(from_date, to_date, |scraper, date| async move {
+            scraper.get_delivery(date).await
+        })
+        .await
+    }
+
+    /// Run `fetch` for every weekday in the range, at most
+    /// [`MAX_ARCHIVE_DOWNLOADS`] at a time, and concatenate the results in
+    /// date order. Days NSE has no file for are skipped as holidays, but if
+    /// every weekday is missing the range fails with [`NseScraperError::NotFound`].
+    async fn get_daily_archives<T, F, Fut>(&self, from_date: NaiveDate, to_date: NaiveDate, fetch: F) -> Result<Vec<T>>
+    where
+        T: Send + 'static,
+        F: Fn(NseScraper, NaiveDate) -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<T>>> + Send + 'static,
+    {
+        let weekdays = dates::weekdays(from_date, to_date);
+        let mut pending = weekdays.iter().copied();
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut days = Vec::with_capacity(weekdays.len());
+        let mut missing = 0;
+
+        loop {
+            while tasks.len() < MAX_ARCHIVE_DOWNLOADS {
+                let Some(date) = pending.next() else { break };
+                let day = fetch(self.clone(), date);
+                tasks.spawn(async move { (date, day.await) });
+            }
+
+            let Some(joined) = tasks.join_next().await else { break };
+            match joined? {
+                (date, Ok(rows)) => days.push((date, rows)),
+                (_, Err(NseScraperError::NotFound { .. })) => missing += 1,
+                (_, Err(e)) => return Err(e),
+            }
+        }
+
+        if !weekdays.is_empty() && missing == weekdays.len() {
+            return Err(NseScraperError::NotFound { symbol: None });
+        }
+        days.sort_by_key(|(date, _)| *date);
+
+        Ok(days.into_iter().flat_map(|(_, rows)| rows).collect())
+    }
+
+    /// Get the option chain of an index such as NIFTY or BANKNIFTY, for all expiries
+    pub async fn get_option_chain_index(&self, index: &Symbol) -> Result<models::OptionChain> {
+        self.get_symbol_json(Endpoint::OptionChainIndices, index.as_str(), &[("symbol", index.as_str())]).await
+    }
+
+    /// Get the option chain of a stock, for all expiries
+    pub async fn get_option_chain_equity(&self, symbol: &Symbol) -> Result<models::OptionChain> {
+        self.get_symbol_json(Endpoint::OptionChainEquities, symbol.as_str(), &[("symbol", symbol.as_str())]).await
+    }
+
+    /// Fetch an API endpoint and decode its JSON body
+    async fn get_json<T: DeserializeOwned>(&self, endpoint: Endpoint, query: &[(&str, &str)]) -> Result<T> {
+        let body = self.fetch(endpoint, query, None).await?;
+        decode(endpoint, &body)
+    }
+
+    /// Fetch an endpoint that describes a single symbol.
+    ///
+    /// NSE answers unknown symbols with 200 and an empty or "not found"
+    /// payload; those are reported as [`NseScraperError::InvalidSymbol`].
+    async fn get_symbol_json<T: DeserializeOwned>(&self, endpoint: Endpoint, symbol: &str, query: &[(&str, &str)]) -> Result<T> {
+        let body = self.fetch(endpoint, query, Some(symbol)).await?;
+        if is_missing_symbol_payload(&body) {
+            return Err(NseScraperError::InvalidSymbol(symbol.to_string()));
+        }
+        decode(endpoint, &body)
+    }
+
+    /// Send a GET request and return the body of the successful response.
+    ///
+    /// The NSE session is primed before the first call. If NSE rejects the
+    /// request with 401/403 the session is treated as expired, re-primed and
+    /// the request repeated once; that replay does not count against the
+    /// [`RetryPolicy`]. Retryable failures, including those of the priming
+    /// request itself, are retried according to the policy.
+    async fn fetch(&self, endpoint: Endpoint, query: &[(&str, &str)], symbol: Option<&str>) -> Result<String> {
+        let body = self.fetch_bytes(&self.endpoints.url(endpoint), query, symbol).await?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    /// [`fetch`](Self::fetch) for an absolute URL, returning the raw body
+    async fn fetch_bytes(&self, url: &str, query: &[(&str, &str)], symbol: Option<&str>) -> Result<Vec<u8>> {
+        let mut attempts = 0;
+        let mut throttled = Duration::ZERO;
+        // Session generation NSE rejected, if any; 0 asks for any primed session
+        let mut stale_session = 0;
+        let mut session_refreshed = false;
+
+        loop {
+            attempts += 1;
+            // Attempts spent on the policy's budget; the one replay after a refresh is free
+            let retries_used = attempts - u32::from(session_refreshed);
+
+            let (error, retry_after) = match self.attempt(url, query, stale_session, &mut throttled).await {
+                Ok((session, response)) if is_session_rejected(response.status()) && !session_refreshed => {
+                    session_refreshed = true;
+                    stale_session = session;
+                    continue;
+                }
+                Ok((_, response)) if response.status().is_success() => match response.bytes().await {
+                    Ok(body) => {
+                        self.stats.record_call(attempts, throttled);
+                        return Ok(body.to_vec());
+                    }
+                    Err(e) => (NseScraperError::from(e), None),
+                },
+                Ok((_, response)) => {
+                    let retry_after = retry::retry_after(response.headers());
+                    let error = NseScraperError::from_status(response.status(), retry_after, symbol);
+                    (error, retry_after)
+                }
+                Err(error) => {
+                    // A priming request NSE throttled tells us how long to back off
+                    let retry_after = match error {
+                        NseScraperError::RateLimited { retry_after } => retry_after,
+                        _ => None,
+                    };
+                    (error, retry_after)
+                }
+            };
+
+            if !error.is_retryable() || !self.retry_policy.allows_retry(retries_used) {
+                self.stats.record_call(attempts, throttled);
+                return Err(if retries_used > 1 {
+                    NseScraperError::RetriesExhausted {
+                        attempts,
+                        source: Box::new(error),
+                    }
+                } else {
+                    error
+                });
+            }
+
+            tokio::time::sleep(self.retry_policy.delay_for(retries_used, retry_after)).await;
+        }
+    }
+
+    /// One attempt: make sure a session newer than `stale_session` is primed,
+    /// then send the request. Returns the session generation it was sent with.
+    async fn attempt(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+        stale_session: u64,
+        throttled: &mut Duration,
+    ) -> Result<(u64, reqwest::Response)> {
+        let session = self.ensure_session(stale_session, throttled).await?;
+        *throttled += self.rate_limiter.acquire().await;
+        let response = self.client.get(url).query(query).send().await?;
+        Ok((session, response))
+    }
+
+    /// Return the current session generation, priming a new session if the
+    /// current one is `stale` (0 before the first priming).
+    ///
+    /// Concurrent callers rejected with the same session wait on the lock and
+    /// reuse the session the first of them primed instead of priming again.
+    async fn ensure_session(&self, stale: u64, throttled: &mut Duration) -> Result<u64> {
+        let mut generation = self.session.lock().await;
+        if *generation != stale {
+            return Ok(*generation);
+        }
+        self.prime_session(throttled).await?;
+        *generation += 1;
+        Ok(*generation)
+    }
+
+    /// Visit the NSE home page so the cookie jar picks up the
+    /// `nsit`/`nseappid`/`bm_sv` cookies the API endpoints require.
+    async fn prime_session(&self, throttled: &mut Duration) -> Result<()> {
+        *throttled += self.rate_limiter.acquire().await;
         let response = self.client
-            .get(&url)
+            .get(self.endpoints.url(Endpoint::Home))
+            .header(ACCEPT, "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8")
             .send()
             .await?;
-            
+
         if !response.status().is_success() {
-            return Err(NseScraperError::ApiError(format!(
-                "API returned error status: {}", response.status()
-            )));
+            let retry_after = retry::retry_after(response.headers());
+            return Err(NseScraperError::from_status(response.status(), retry_after, None));
         }
-        
-        let data = response.json::<models::HistoricalData>().await?;
-        Ok(data)
+
+        Ok(())
+    }
+}
+
+/// Normalise an index name such as "nifty 50" for NSE's index endpoints
+fn index_name(index: &str) -> Result<String> {
+    let name = index.split_whitespace().collect::<Vec<_>>().join(" ").to_uppercase();
+    if name.is_empty() {
+        return Err(NseScraperError::InvalidSymbol(index.to_string()));
+    }
+    Ok(name)
+}
+
+/// Query of the corporate filing endpoints: segment, symbol and date range
+fn filing_query(filter: &models::FilingFilter, extra: &[(&'static str, &str)]) -> Vec<(&'static str, String)> {
+    let mut query = vec![("index", "equities".to_string())];
+    query.extend(extra.iter().map(|(key, value)| (*key, value.to_string())));
+    if let Some(symbol) = &filter.symbol {
+        query.push(("symbol", symbol.to_string()));
+    }
+    if let (Some(from), Some(to)) = (filter.from_date, filter.to_date) {
+        query.push(("from_date", from.format("%d-%m-%Y").to_string()));
+        query.push(("to_date", to.format("%d-%m-%Y").to_string()));
+    }
+    query
+}
+
+/// Query of the historical deal endpoints
+fn deal_query(symbol: Option<&str>, from: NaiveDate, to: NaiveDate) -> Vec<(&'static str, String)> {
+    let mut query = vec![
+        ("from", from.format("%d-%m-%Y").to_string()),
+        ("to", to.format("%d-%m-%Y").to_string()),
+    ];
+    if let Some(symbol) = symbol {
+        query.push(("symbol", symbol.to_string()));
+    }
+    query
+}
+
+fn query_refs<'a>(query: &'a [(&'static str, String)]) -> Vec<(&'a str, &'a str)> {
+    query.iter().map(|(key, value)| (*key, value.as_str())).collect()
+}
+
+fn is_session_rejected(status: StatusCode) -> bool {
+    status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN
+}
+
+/// Split `from..=to` into consecutive inclusive windows of at most `max_days` days
+fn date_windows(from: NaiveDate, to: NaiveDate, max_days: i64) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut windows = Vec::new();
+    let mut start = from;
+    while start <= to {
+        let end = (start + chrono::Duration::days(max_days - 1)).min(to);
+        windows.push((start, end));
+        start = end + chrono::Duration::days(1);
+    }
+    windows
+}
+
+fn decode<T: DeserializeOwned>(endpoint: Endpoint, body: &str) -> Result<T> {
+    serde_json::from_str(body).map_err(|e| NseScraperError::decode(endpoint, body, e))
+}
+
+/// Whether a successful response is NSE's way of saying the symbol does not exist:
+/// an empty body, object or array, or an object whose `error` or `message` field
+/// names the symbol as unknown. A generic "no data found" is not enough, since
+/// NSE also sends it for a valid symbol with nothing in the requested range.
+fn is_missing_symbol_payload(body: &str) -> bool {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.trim().is_empty();
+    };
+
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::Array(items) => items.is_empty(),
+        serde_json::Value::Object(fields) => {
+            fields.is_empty()
+                || ["error", "message"].iter().any(|field| {
+                    fields
+                        .get(*field)
+                        .and_then(serde_json::Value::as_str)
+                        .is_some_and(is_missing_symbol_message)
+                })
+        }
+        _ => false,
     }
-}
\ No newline at end of file
+}
+
+fn is_missing_symbol_message(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    ["symbol not found", "invalid symbol"]
+        .iter()
+        .any(|marker| message.contains(marker))
+}
@@ -1,19 +1,46 @@
 // src/models.rs
 use serde::{Deserialize, Serialize};
-use chrono::NaiveDate;
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate};
+use crate::{dates, numbers};
 
-fn parse_date_string(date_str: &str) -> Option<NaiveDate> {
-    let parts: Vec<&str> = date_str.split('-').collect();
-    if parts.len() != 3 {
-        return None;
-    }
-    
-    let day = parts[0].parse::<u32>().ok()?;
-    let month = parts[1].parse::<u32>().ok()?;
-    let year = parts[2].parse::<i32>().ok()?;
-    
-    NaiveDate::from_ymd_opt(year, month, day)
-}
+pub mod corporate_actions;
+pub mod deals;
+pub mod derivatives;
+pub mod filings;
+pub mod indices;
+pub mod insider;
+pub mod movers;
+pub mod option_chain;
+pub mod pre_open;
+pub mod vix;
+
+pub use corporate_actions::{CorporateAction, CorporateActionKind};
+pub use deals::{Deal, DealDay, DealRecords, DealSide, LargeDeals, ShortSale, ShortSellingRecords};
+pub use derivatives::{
+    ContractKind, ContractMarketDepth, ContractMetadata, ContractOtherInfo, ContractTradeInfo,
+    DerivativeContract, DerivativeInfo, DerivativeQuote, FuturesContract, OptionContract,
+};
+pub use filings::{
+    Announcement, BoardMeeting, Filing, FilingCategory, FilingCursor, FilingFilter, FinancialResult, ResultPeriod,
+};
+pub use indices::{
+    IndexCloseRecord, IndexConstituent, IndexHistory, IndexHistoryRecords, IndexStockRow, IndexStocks,
+    IndexTurnoverRecord,
+};
+pub use insider::{InsiderSide, InsiderTrade, InsiderTrades, PersonCategory, SastDisclosure, SastDisclosures};
+pub use movers::{
+    MarketMovers, MostActive, MostActiveRecord, MoverRow, MoversIndex, PriceBandGroup, PriceBandGroups,
+    PriceBandHitters, PriceBandRecord, VariationGroup, VariationRecord, Variations, YearExtremeRecord,
+    YearExtremes,
+};
+pub use option_chain::{
+    FilteredOptionChain, OptionChain, OptionChainFilter, OptionChainRecords, OptionChainRow,
+    OptionLeg, OptionTotals,
+};
+pub use pre_open::{
+    PreOpenBook, PreOpenDetail, PreOpenLevel, PreOpenMarket, PreOpenMetadata, PreOpenRow, PreOpenSegment,
+};
+pub use vix::{IndiaVix, VixHistory, VixRecord, INDIA_VIX};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct QuoteData {
@@ -59,27 +86,27 @@ pub struct StockInfo {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PriceInfo {
-    #[serde(rename = "lastPrice")]
+    #[serde(rename = "lastPrice", with = "numbers::required")]
     pub last_price: f64,
-    #[serde(rename = "change")]
+    #[serde(rename = "change", with = "numbers::required")]
     pub change: f64,
-    #[serde(rename = "pChange")]
+    #[serde(rename = "pChange", with = "numbers::required")]
     pub p_change: f64,
-    #[serde(rename = "previousClose")]
+    #[serde(rename = "previousClose", with = "numbers::required")]
     pub previous_close: f64,
-    #[serde(rename = "open")]
+    #[serde(rename = "open", with = "numbers::required")]
     pub open: f64,
-    #[serde(rename = "close")]
+    #[serde(rename = "close", default, with = "numbers::option")]
     pub close: Option<f64>,
-    #[serde(rename = "vwap")]
-    pub vwap: f64,
+    #[serde(rename = "vwap", default, with = "numbers::option")]
+    pub vwap: Option<f64>,
     #[serde(rename = "lowerCP")]
     pub lower_cp: Option<String>,
     #[serde(rename = "upperCP")]
     pub upper_cp: Option<String>,
     #[serde(rename = "pPriceBand")]
     pub p_price_band: String,
-    #[serde(rename = "basePrice")]
+    #[serde(rename = "basePrice", with = "numbers::required")]
     pub base_price: f64,
     #[serde(rename = "intraDayHighLow")]
     pub intra_day_high_low: IntraDayHighLow,
@@ -89,24 +116,24 @@ pub struct PriceInfo {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IntraDayHighLow {
-    #[serde(rename = "min")]
+    #[serde(rename = "min", with = "numbers::required")]
     pub min: f64,
-    #[serde(rename = "max")]
+    #[serde(rename = "max", with = "numbers::required")]
     pub max: f64,
-    #[serde(rename = "value")]
+    #[serde(rename = "value", default, with = "numbers::option")]
     pub value: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WeekHighLow {
-    #[serde(rename = "min")]
+    #[serde(rename = "min", with = "numbers::required")]
     pub min: f64,
-    #[serde(rename = "max")]
+    #[serde(rename = "max", with = "numbers::required")]
     pub max: f64,
-    #[serde(rename = "minDate")]
-    pub min_date: String,
-    #[serde(rename = "maxDate")]
-    pub max_date: String,
+    #[serde(rename = "minDate", with = "dates::nse_date")]
+    pub min_date: NaiveDate,
+    #[serde(rename = "maxDate", with = "dates::nse_date")]
+    pub max_date: NaiveDate,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -127,9 +154,9 @@ pub struct SecurityInfo {
     pub derivatives: String,
     #[serde(rename = "surveillance")]
     pub surveillance: String,
-    #[serde(rename = "faceValue")]
+    #[serde(rename = "faceValue", with = "numbers::required")]
     pub face_value: f64,
-    #[serde(rename = "issuedSize")]
+    #[serde(rename = "issuedSize", with = "numbers::required")]
     pub issued_size: u64,
 }
 
@@ -143,16 +170,16 @@ pub struct Metadata {
     pub isin: String,
     #[serde(rename = "status")]
     pub status: String,
-    #[serde(rename = "listingDate")]
-    pub listing_date: String,
+    #[serde(rename = "listingDate", with = "dates::nse_date")]
+    pub listing_date: NaiveDate,
     #[serde(rename = "industry")]
     pub industry: String,
-    #[serde(rename = "lastUpdateTime")]
-    pub last_update_time: String,
+    #[serde(rename = "lastUpdateTime", with = "dates::ist_datetime")]
+    pub last_update_time: DateTime<FixedOffset>,
     #[serde(rename = "pdSectorInd")]
     pub pd_sector_ind: String,
-    #[serde(rename = "pdSectorPe")]
-    pub pd_sector_pe: f64,
+    #[serde(rename = "pdSectorPe", default, with = "numbers::option")]
+    pub pd_sector_pe: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -167,47 +194,47 @@ pub struct MarketDepth {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DepthOrder {
-    #[serde(rename = "price")]
+    #[serde(rename = "price", with = "numbers::required")]
     pub price: f64,
-    #[serde(rename = "quantity")]
+    #[serde(rename = "quantity", with = "numbers::required")]
     pub quantity: u32,
-    #[serde(rename = "orders")]
+    #[serde(rename = "orders", with = "numbers::required")]
     pub orders: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TradeInfo {
-    #[serde(rename = "totalBuyQuantity")]
+    #[serde(rename = "totalBuyQuantity", with = "numbers::required")]
     pub total_buy_quantity: u64,
-    #[serde(rename = "totalSellQuantity")]
+    #[serde(rename = "totalSellQuantity", with = "numbers::required")]
     pub total_sell_quantity: u64,
-    #[serde(rename = "totalTradedValue")]
+    #[serde(rename = "totalTradedValue", with = "numbers::required")]
     pub total_traded_value: f64,
-    #[serde(rename = "totalTradedVolume")]
+    #[serde(rename = "totalTradedVolume", with = "numbers::required")]
     pub total_traded_volume: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TradingInfo {
-    #[serde(rename = "totalTradedVolume")]
+    #[serde(rename = "totalTradedVolume", with = "numbers::required")]
     pub total_traded_volume: u64,
-    #[serde(rename = "totalTradedValue")]
+    #[serde(rename = "totalTradedValue", with = "numbers::required")]
     pub total_traded_value: f64,
-    #[serde(rename = "totalMarketCap")]
-    pub total_market_cap: f64,
-    #[serde(rename = "ffmc")]
-    pub ffmc: f64,
-    #[serde(rename = "impact")]
-    pub impact: f64,
-    #[serde(rename = "deliveryQuantity")]
+    #[serde(rename = "totalMarketCap", default, with = "numbers::option")]
+    pub total_market_cap: Option<f64>,
+    #[serde(rename = "ffmc", default, with = "numbers::option")]
+    pub ffmc: Option<f64>,
+    #[serde(rename = "impact", default, with = "numbers::option")]
+    pub impact: Option<f64>,
+    #[serde(rename = "deliveryQuantity", default, with = "numbers::option")]
     pub delivery_quantity: Option<u64>,
-    #[serde(rename = "deliveryPercentage")]
+    #[serde(rename = "deliveryPercentage", default, with = "numbers::option")]
     pub delivery_percentage: Option<f64>,
-    #[serde(rename = "deliveryToTradedQuantity")]
+    #[serde(rename = "deliveryToTradedQuantity", default, with = "numbers::option")]
     pub delivery_to_traded_quantity: Option<f64>,
-    #[serde(rename = "varMargin")]
+    #[serde(rename = "varMargin", default, with = "numbers::option")]
     pub var_margin: Option<f64>,
-    #[serde(rename = "marketLot")]
+    #[serde(rename = "marketLot", with = "numbers::required")]
     pub market_lot: u32,
 }
 
@@ -229,11 +256,11 @@ pub struct MarketSegmentState {
     pub trade_date: String,
     #[serde(rename = "index")]
     pub index: Option<String>,
-    #[serde(rename = "last")]
+    #[serde(rename = "last", default, with = "numbers::option")]
     pub last: Option<f64>,
-    #[serde(rename = "variation")]
+    #[serde(rename = "variation", default, with = "numbers::option")]
     pub variation: Option<f64>,
-    #[serde(rename = "percentChange")]
+    #[serde(rename = "percentChange", default, with = "numbers::option")]
     pub percent_change: Option<f64>,
 }
 
@@ -253,35 +280,35 @@ pub struct IndexData {
     pub index: String,
     #[serde(rename = "indexSymbol")]
     pub index_symbol: String,
-    #[serde(rename = "last")]
+    #[serde(rename = "last", with = "numbers::required")]
     pub last: f64,
-    #[serde(rename = "variation")]
+    #[serde(rename = "variation", with = "numbers::required")]
     pub variation: f64,
-    #[serde(rename = "percentChange")]
+    #[serde(rename = "percentChange", with = "numbers::required")]
     pub percent_change: f64,
-    #[serde(rename = "open")]
+    #[serde(rename = "open", with = "numbers::required")]
     pub open: f64,
-    #[serde(rename = "high")]
+    #[serde(rename = "high", with = "numbers::required")]
     pub high: f64,
-    #[serde(rename = "low")]
+    #[serde(rename = "low", with = "numbers::required")]
     pub low: f64,
-    #[serde(rename = "previousClose")]
+    #[serde(rename = "previousClose", with = "numbers::required")]
     pub previous_close: f64,
-    #[serde(rename = "yearHigh")]
+    #[serde(rename = "yearHigh", with = "numbers::required")]
     pub year_high: f64,
-    #[serde(rename = "yearLow")]
+    #[serde(rename = "yearLow", with = "numbers::required")]
     pub year_low: f64,
-    #[serde(rename = "pe")]
+    #[serde(rename = "pe", default, with = "numbers::option")]
     pub pe: Option<f64>,
-    #[serde(rename = "pb")]
+    #[serde(rename = "pb", default, with = "numbers::option")]
     pub pb: Option<f64>,
-    #[serde(rename = "dy")]
+    #[serde(rename = "dy", default, with = "numbers::option")]
     pub dy: Option<f64>,
-    #[serde(rename = "declines")]
+    #[serde(rename = "declines", default, with = "numbers::option")]
     pub declines: Option<u32>,
-    #[serde(rename = "advances")]
+    #[serde(rename = "advances", default, with = "numbers::option")]
     pub advances: Option<u32>,
-    #[serde(rename = "unchanged")]
+    #[serde(rename = "unchanged", default, with = "numbers::option")]
     pub unchanged: Option<u32>,
 }
 
@@ -294,29 +321,29 @@ pub struct HistoricalData {
 }
 
 // Update the DailyData struct definition
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct DailyData {
-    #[serde(rename = "CH_TIMESTAMP")]
-    pub timestamp: String,
-    #[serde(rename = "CH_OPENING_PRICE")]
+    #[serde(rename = "CH_TIMESTAMP", with = "dates::iso_date")]
+    pub timestamp: NaiveDate,
+    #[serde(rename = "CH_OPENING_PRICE", with = "numbers::required")]
     pub open: f64,
-    #[serde(rename = "CH_TRADE_HIGH_PRICE")]
+    #[serde(rename = "CH_TRADE_HIGH_PRICE", with = "numbers::required")]
     pub high: f64,
-    #[serde(rename = "CH_TRADE_LOW_PRICE")]
+    #[serde(rename = "CH_TRADE_LOW_PRICE", with = "numbers::required")]
     pub low: f64,
-    #[serde(rename = "CH_CLOSING_PRICE")]
+    #[serde(rename = "CH_CLOSING_PRICE", with = "numbers::required")]
     pub close: f64,
-    #[serde(rename = "CH_LAST_TRADED_PRICE")]
+    #[serde(rename = "CH_LAST_TRADED_PRICE", with = "numbers::required")]
     pub last: f64,
-    #[serde(rename = "CH_PREVIOUS_CLS_PRICE")]
+    #[serde(rename = "CH_PREVIOUS_CLS_PRICE", with = "numbers::required")]
     pub prev_close: f64,
-    #[serde(rename = "CH_TOT_TRADED_QTY")]
+    #[serde(rename = "CH_TOT_TRADED_QTY", with = "numbers::required")]
     pub volume: u64,
-    #[serde(rename = "CH_TOT_TRADED_VAL")]
+    #[serde(rename = "CH_TOT_TRADED_VAL", with = "numbers::required")]
     pub value: f64,
-    #[serde(rename = "CH_52WEEK_HIGH_PRICE")]
+    #[serde(rename = "CH_52WEEK_HIGH_PRICE", with = "numbers::required")]
     pub year_high: f64,
-    #[serde(rename = "CH_52WEEK_LOW_PRICE")]
+    #[serde(rename = "CH_52WEEK_LOW_PRICE", with = "numbers::required")]
     pub year_low: f64,
     
     // Additional fields for technical analysis
@@ -344,6 +371,22 @@ pub struct DailyData {
     pub bollinger_middle: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bollinger_lower: Option<f64>,
+
+    #[serde(rename = "COP_DELIV_QTY", default, skip_serializing_if = "Option::is_none", with = "numbers::option")]
+    pub delivery_qty: Option<u64>,
+    #[serde(rename = "COP_DELIV_PERC", default, skip_serializing_if = "Option::is_none", with = "numbers::option")]
+    pub delivery_pct: Option<f64>,
+
+    /// Cumulative price factor applied by `HistoricalData::adjust_for_corporate_actions`;
+    /// `None` for rows left as traded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adjustment_factor: Option<f64>,
+}
+
+impl DailyData {
+    pub fn is_adjusted(&self) -> bool {
+        self.adjustment_factor.is_some()
+    }
 }
 
 // New struct for time frame selection
@@ -355,6 +398,7 @@ pub enum TimeFrame {
 }
 
 impl TimeFrame {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.trim() {
             "1" => Some(TimeFrame::Daily),
@@ -376,8 +420,8 @@ impl TimeFrame {
 // New struct for consolidated data after time frame conversion
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeFrameData {
-    #[serde(rename = "date")]
-    pub date: String,
+    #[serde(rename = "date", with = "dates::iso_date")]
+    pub date: NaiveDate,
     #[serde(rename = "open")]
     pub open: f64,
     #[serde(rename = "high")]
@@ -390,13 +434,50 @@ pub struct TimeFrameData {
     pub volume: u64,
     #[serde(rename = "value")]
     pub value: f64,
+    /// Only set when every day in the period has delivery data
+    #[serde(rename = "delivery_qty", default, skip_serializing_if = "Option::is_none")]
+    pub delivery_qty: Option<u64>,
+    #[serde(rename = "delivery_pct", default, skip_serializing_if = "Option::is_none")]
+    pub delivery_pct: Option<f64>,
+}
+
+impl TimeFrameData {
+    fn from_day(day: &DailyData) -> Self {
+        TimeFrameData {
+            date: day.timestamp,
+            open: day.open,
+            high: day.high,
+            low: day.low,
+            close: day.close,
+            volume: day.volume,
+            value: day.value,
+            delivery_qty: day.delivery_qty,
+            delivery_pct: day.delivery_pct.or_else(|| delivery_pct(day.delivery_qty, day.volume)),
+        }
+    }
+
+    fn add_day(&mut self, day: &DailyData) {
+        self.high = self.high.max(day.high);
+        self.low = self.low.min(day.low);
+        self.close = day.close; // Last day's close
+        self.volume += day.volume;
+        self.value += day.value;
+        // Weighted by traded quantity, not an average of the daily percentages
+        self.delivery_qty = self.delivery_qty.zip(day.delivery_qty).map(|(a, b)| a + b);
+        self.delivery_pct = delivery_pct(self.delivery_qty, self.volume);
+    }
+}
+
+fn delivery_pct(delivery_qty: Option<u64>, volume: u64) -> Option<f64> {
+    let qty = delivery_qty?;
+    (volume > 0).then(|| qty as f64 / volume as f64 * 100.0)
 }
 
 // New struct for technical indicators
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TechnicalIndicators {
-    #[serde(rename = "date")]
-    pub date: String,
+    #[serde(rename = "date", with = "dates::iso_date")]
+    pub date: NaiveDate,
     #[serde(rename = "rsi_14")]
     pub rsi_14: Option<f64>,
     #[serde(rename = "sma_20")]
@@ -430,10 +511,10 @@ pub struct ConsolidatedData {
     pub symbol: String,
     #[serde(rename = "timeFrame")]
     pub time_frame: String,
-    #[serde(rename = "fromDate")]
-    pub from_date: String,
-    #[serde(rename = "toDate")]
-    pub to_date: String,
+    #[serde(rename = "fromDate", with = "dates::option_iso_date")]
+    pub from_date: Option<NaiveDate>,
+    #[serde(rename = "toDate", with = "dates::option_iso_date")]
+    pub to_date: Option<NaiveDate>,
     #[serde(rename = "data")]
     pub data: Vec<TimeFrameData>,
     #[serde(rename = "indicators")]
@@ -442,6 +523,25 @@ pub struct ConsolidatedData {
 
 // Helper functions for time frame conversion
 impl HistoricalData {
+    /// Recompute `year_high`/`year_low` over the trailing 365 days of the
+    /// rows held, for sources that do not publish a 52-week range. The
+    /// figures are only exact once a full year of rows is present.
+    pub fn fill_year_range(&mut self) {
+        self.data.sort_by_key(|d| d.timestamp);
+
+        for i in 0..self.data.len() {
+            let year_ago = self.data[i].timestamp - chrono::Duration::days(365);
+            let (year_high, year_low) = self.data[..=i]
+                .iter()
+                .rev()
+                .take_while(|d| d.timestamp > year_ago)
+                .fold((f64::MIN, f64::MAX), |(high, low), d| (high.max(d.high), low.min(d.low)));
+
+            self.data[i].year_high = year_high;
+            self.data[i].year_low = year_low;
+        }
+    }
+
     pub fn to_time_frame(&self, time_frame: TimeFrame) -> ConsolidatedData {
         match time_frame {
             TimeFrame::Daily => self.to_daily(),
@@ -454,20 +554,12 @@ impl HistoricalData {
         let mut data = Vec::new();
         
         for day in &self.data {
-            data.push(TimeFrameData {
-                date: day.timestamp.clone(),
-                open: day.open,
-                high: day.high,
-                low: day.low,
-                close: day.close,
-                volume: day.volume,
-                value: day.value,
-            });
+            data.push(TimeFrameData::from_day(day));
         }
         
         // Get date range
-        let from_date = self.data.first().map(|d| d.timestamp.clone()).unwrap_or_default();
-        let to_date = self.data.last().map(|d| d.timestamp.clone()).unwrap_or_default();
+        let from_date = self.data.first().map(|d| d.timestamp);
+        let to_date = self.data.last().map(|d| d.timestamp);
         
         ConsolidatedData {
             symbol: self.symbol.clone(),
@@ -481,12 +573,7 @@ impl HistoricalData {
     
     fn prepare_sorted_data(&self) -> Vec<DailyData> {
         let mut sorted_data = self.data.clone();
-        sorted_data.sort_by(|a, b| {
-            let date_a = parse_date_string(&a.timestamp);
-            let date_b = parse_date_string(&b.timestamp);
-            
-            date_a.cmp(&date_b)
-        });
+        sorted_data.sort_by_key(|d| d.timestamp);
         sorted_data
     }
 
@@ -496,8 +583,8 @@ impl HistoricalData {
             return ConsolidatedData {
                 symbol: self.symbol.clone(),
                 time_frame: "weekly".to_string(),
-                from_date: String::new(),
-                to_date: String::new(),
+                from_date: None,
+                to_date: None,
                 data: weekly_data,
                 indicators: None,
             };
@@ -507,66 +594,29 @@ impl HistoricalData {
         let sorted_data = self.prepare_sorted_data();
         
         // Get date range - make sure to capture this before consuming sorted_data
-        let from_date = sorted_data.first().map(|d| d.timestamp.clone()).unwrap_or_default();
-        let to_date = sorted_data.last().map(|d| d.timestamp.clone()).unwrap_or_default();
+        let from_date = sorted_data.first().map(|d| d.timestamp);
+        let to_date = sorted_data.last().map(|d| d.timestamp);
         
         // Group by week
         let mut current_week: Option<(TimeFrameData, NaiveDate)> = None;
         
         for day in sorted_data {
-            let date_str = &day.timestamp;
-            let parts: Vec<&str> = date_str.split('-').collect();
-            if parts.len() != 3 {
-                continue;
-            }
-            
-            let day_val = parts[0].parse::<u32>().unwrap_or(1);
-            let month = parts[1].parse::<u32>().unwrap_or(1);
-            let year = parts[2].parse::<i32>().unwrap_or(2023);
-            
-            if let Some(date) = NaiveDate::from_ymd_opt(year, month, day_val) {
-                match &mut current_week {
-                    None => {
-                        // Start a new week
-                        current_week = Some((
-                            TimeFrameData {
-                                date: day.timestamp.clone(),
-                                open: day.open,
-                                high: day.high,
-                                low: day.low,
-                                close: day.close,
-                                volume: day.volume,
-                                value: day.value,
-                            },
-                            date
-                        ));
-                    }
-                    Some((week_data, week_start)) => {
-                        // Check if this is a new week
-                        let days_diff = date.signed_duration_since(*week_start).num_days();
-                        if days_diff >= 7 {
-                            // Push current week and start a new one
-                            weekly_data.push(week_data.clone());
-                            current_week = Some((
-                                TimeFrameData {
-                                    date: day.timestamp.clone(),
-                                    open: day.open,
-                                    high: day.high,
-                                    low: day.low,
-                                    close: day.close,
-                                    volume: day.volume,
-                                    value: day.value,
-                                },
-                                date
-                            ));
-                        } else {
-                            // Update current week
-                            week_data.high = week_data.high.max(day.high);
-                            week_data.low = week_data.low.min(day.low);
-                            week_data.close = day.close; // Last day's close
-                            week_data.volume += day.volume;
-                            week_data.value += day.value;
-                        }
+            let date = day.timestamp;
+            match &mut current_week {
+                None => {
+                    // Start a new week
+                    current_week = Some((TimeFrameData::from_day(&day), date));
+                }
+                Some((week_data, week_start)) => {
+                    // Check if this is a new week
+                    let days_diff = date.signed_duration_since(*week_start).num_days();
+                    if days_diff >= 7 {
+                        // Push current week and start a new one
+                        weekly_data.push(week_data.clone());
+                        current_week = Some((TimeFrameData::from_day(&day), date));
+                    } else {
+                        // Update current week
+                        week_data.add_day(&day);
                     }
                 }
             }
@@ -593,8 +643,8 @@ impl HistoricalData {
             return ConsolidatedData {
                 symbol: self.symbol.clone(),
                 time_frame: "monthly".to_string(),
-                from_date: String::new(),
-                to_date: String::new(),
+                from_date: None,
+                to_date: None,
                 data: monthly_data,
                 indicators: None,
             };
@@ -604,63 +654,31 @@ impl HistoricalData {
         let sorted_data = self.prepare_sorted_data();
         
         // Get date range - make sure to capture this before consuming sorted_data
-        let from_date = sorted_data.first().map(|d| d.timestamp.clone()).unwrap_or_default();
-        let to_date = sorted_data.last().map(|d| d.timestamp.clone()).unwrap_or_default();
+        let from_date = sorted_data.first().map(|d| d.timestamp);
+        let to_date = sorted_data.last().map(|d| d.timestamp);
         
         // Group by month
         let mut current_month: Option<(TimeFrameData, (u32, i32))> = None;
         
         for day in sorted_data {
-            let date_str = &day.timestamp;
-            let parts: Vec<&str> = date_str.split('-').collect();
-            if parts.len() != 3 {
-                continue;
-            }
-            
-            let _day_val = parts[0].parse::<u32>().unwrap_or_default();
-            let month = parts[1].parse::<u32>().unwrap_or_default();
-            let year = parts[2].parse::<i32>().unwrap_or_default();
+            let month = day.timestamp.month();
+            let year = day.timestamp.year();
             
             match &mut current_month {
                 None => {
                     // Start a new month
-                    current_month = Some((
-                        TimeFrameData {
-                            date: format!("{}-{}", month, year), // Format as MM-YYYY
-                            open: day.open,
-                            high: day.high,
-                            low: day.low,
-                            close: day.close,
-                            volume: day.volume,
-                            value: day.value,
-                        },
-                        (month, year)
-                    ));
+                    // Dated by the first trading day of the month
+                    current_month = Some((TimeFrameData::from_day(&day), (month, year)));
                 }
                 Some((month_data, (current_month_num, current_year))) => {
                     // Check if this is a new month
                     if month != *current_month_num || year != *current_year {
                         // Push current month and start a new one
                         monthly_data.push(month_data.clone());
-                        current_month = Some((
-                            TimeFrameData {
-                                date: format!("{}-{}", month, year),
-                                open: day.open,
-                                high: day.high,
-                                low: day.low,
-                                close: day.close,
-                                volume: day.volume,
-                                value: day.value,
-                            },
-                            (month, year)
-                        ));
+                        current_month = Some((TimeFrameData::from_day(&day), (month, year)));
                     } else {
                         // Update current month
-                        month_data.high = month_data.high.max(day.high);
-                        month_data.low = month_data.low.min(day.low);
-                        month_data.close = day.close; // Last day's close
-                        month_data.volume += day.volume;
-                        month_data.value += day.value;
+                        month_data.add_day(&day);
                     }
                 }
             }
@@ -688,12 +706,7 @@ impl HistoricalData {
         }
         
         // Sort data by date
-        self.data.sort_by(|a, b| {
-            let date_a = parse_date_string(&a.timestamp);
-            let date_b = parse_date_string(&b.timestamp);
-            
-            date_a.cmp(&date_b)
-        });
+        self.data.sort_by_key(|d| d.timestamp);
         
         // Calculate SMA
         self.calculate_sma(20);
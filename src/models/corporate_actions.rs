@@ -0,0 +1,240 @@
+// src/models/corporate_actions.rs
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use crate::{dates, numbers};
+use super::HistoricalData;
+
+/// A corporate action as listed by NSE's `corporates-corporateActions` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorporateAction {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "series", default)]
+    pub series: Option<String>,
+    #[serde(rename = "faceVal", default, with = "numbers::option")]
+    pub face_value: Option<f64>,
+    /// Free-text description, e.g. "Bonus 1:1" or "Dividend - Rs 10 Per Share"
+    #[serde(rename = "subject")]
+    pub subject: String,
+    #[serde(rename = "exDate", default, with = "dates::option_nse_date")]
+    pub ex_date: Option<NaiveDate>,
+    #[serde(rename = "recDate", default, with = "dates::option_nse_date")]
+    pub record_date: Option<NaiveDate>,
+    #[serde(rename = "comp", default)]
+    pub company: Option<String>,
+    #[serde(rename = "isin", default)]
+    pub isin: Option<String>,
+}
+
+/// What a corporate action does to the share price, parsed from its subject
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CorporateActionKind {
+    /// Face value changed from `from` to `to`
+    Split { from: f64, to: f64 },
+    /// `new` bonus shares for every `held`
+    Bonus { new: f64, held: f64 },
+    /// `new` shares offered for every `held` at `price`, when NSE states it
+    Rights { new: f64, held: f64, price: Option<f64> },
+    /// Cash dividend per share
+    Dividend { amount: f64 },
+    Other,
+}
+
+impl CorporateAction {
+    pub fn kind(&self) -> CorporateActionKind {
+        let subject = self.subject.to_ascii_lowercase();
+
+        if subject.contains("split") || subject.contains("sub-division") || subject.contains("sub division") {
+            let values = numbers_in(&subject);
+            if let [from, to, ..] = values[..] {
+                if from > 0.0 && to > 0.0 {
+                    return CorporateActionKind::Split { from, to };
+                }
+            }
+        }
+
+        if subject.contains("bonus") {
+            if let Some((new, held)) = ratio_in(&subject) {
+                return CorporateActionKind::Bonus { new, held };
+            }
+        }
+
+        if subject.contains("rights") {
+            if let Some((new, held)) = ratio_in(&subject) {
+                let price = subject.split_once('@').and_then(|(_, terms)| {
+                    let quoted = numbers_in(terms).first().copied()?;
+                    if terms.contains("premium") {
+                        Some(quoted + self.face_value.unwrap_or(0.0))
+                    } else {
+                        Some(quoted)
+                    }
+                });
+                return CorporateActionKind::Rights { new, held, price };
+            }
+        }
+
+        if subject.contains("dividend") {
+            if let Some(amount) = self.dividend_amount(&subject) {
+                return CorporateActionKind::Dividend { amount };
+            }
+        }
+
+        CorporateActionKind::Other
+    }
+
+    /// Sum of the rupee amounts in a dividend subject; percentages are read against face value
+    fn dividend_amount(&self, subject: &str) -> Option<f64> {
+        let mut total = 0.0;
+        let mut found = false;
+
+        for part in subject.split(" and ") {
+            let amount = rupee_amount(part).or_else(|| {
+                let percent = part.find('%')?;
+                let pct = numbers_in(&part[..percent]).last().copied()?;
+                Some(pct / 100.0 * self.face_value?)
+            });
+
+            if let Some(amount) = amount {
+                total += amount;
+                found = true;
+            }
+        }
+
+        found.then_some(total)
+    }
+
+    /// Multipliers applied to prices and volumes before the ex-date, given
+    /// the last close before it. `None` if the action does not adjust prices.
+    pub fn adjustment_factors(&self, cum_price: f64, include_dividends: bool) -> Option<(f64, f64)> {
+        match self.kind() {
+            CorporateActionKind::Split { from, to } => Some((to / from, from / to)),
+            CorporateActionKind::Bonus { new, held } if held > 0.0 => {
+                Some((held / (new + held), (new + held) / held))
+            }
+            CorporateActionKind::Rights { new, held, price: Some(price) }
+                if held > 0.0 && cum_price > price =>
+            {
+                let terp = (held * cum_price + new * price) / (new + held);
+                let factor = terp / cum_price;
+                Some((factor, 1.0 / factor))
+            }
+            CorporateActionKind::Dividend { amount }
+                if include_dividends && cum_price > amount =>
+            {
+                Some(((cum_price - amount) / cum_price, 1.0))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl HistoricalData {
+    /// Back-adjust prices and volumes for splits, bonuses and rights issues,
+    /// and optionally for dividends.
+    ///
+    /// Every row before an action's ex-date is scaled so the series is
+    /// continuous across it. Rights and dividends with an ex-date after the
+    /// last row are skipped, since their factor needs the close before the
+    /// ex-date. Delivered quantity is scaled with volume, so
+    /// delivery percentages are unchanged. Adjusted rows carry the cumulative
+    /// price factor in `adjustment_factor`. Traded value is left untouched.
+    pub fn adjust_for_corporate_actions(&mut self, actions: &[CorporateAction], include_dividends: bool) {
+        self.data.sort_by_key(|d| d.timestamp);
+
+        let mut actions: Vec<&CorporateAction> = actions
+            .iter()
+            .filter(|a| a.ex_date.is_some())
+            .collect();
+        actions.sort_by_key(|a| a.ex_date);
+
+        // Every factor is taken from the unadjusted closes, so actions sharing
+        // an ex-date, such as a bonus and a dividend, do not see each other
+        let adjustments: Vec<(usize, f64, f64)> = actions
+            .iter()
+            .filter_map(|action| {
+                let ex_date = action.ex_date?;
+                let before = self.data.partition_point(|d| d.timestamp < ex_date);
+                if before == 0 {
+                    return None;
+                }
+
+                // Past the last row the close before the ex-date is unknown,
+                // and rights and dividends depend on it
+                let needs_cum_price = matches!(
+                    action.kind(),
+                    CorporateActionKind::Rights { .. } | CorporateActionKind::Dividend { .. }
+                );
+                if before == self.data.len() && needs_cum_price {
+                    return None;
+                }
+
+                let cum_price = self.data[before - 1].close;
+                let (price_factor, volume_factor) = action.adjustment_factors(cum_price, include_dividends)?;
+                Some((before, price_factor, volume_factor))
+            })
+            .collect();
+
+        for (before, price_factor, volume_factor) in adjustments {
+            for day in &mut self.data[..before] {
+                day.open *= price_factor;
+                day.high *= price_factor;
+                day.low *= price_factor;
+                day.close *= price_factor;
+                day.last *= price_factor;
+                day.prev_close *= price_factor;
+                day.year_high *= price_factor;
+                day.year_low *= price_factor;
+                day.volume = (day.volume as f64 * volume_factor).round() as u64;
+                day.delivery_qty = day.delivery_qty.map(|qty| (qty as f64 * volume_factor).round() as u64);
+                day.adjustment_factor = Some(day.adjustment_factor.unwrap_or(1.0) * price_factor);
+            }
+        }
+    }
+}
+
+/// Decimal numbers appearing in `s`, in order
+fn numbers_in(s: &str) -> Vec<f64> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+
+    for c in s.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_digit() || (c == '.' && !current.is_empty() && !current.contains('.')) {
+            current.push(c);
+        } else if !current.is_empty() {
+            if let Ok(v) = current.trim_end_matches('.').parse() {
+                values.push(v);
+            }
+            current.clear();
+        }
+    }
+
+    values
+}
+
+/// Amount following the first rupee marker in `s`, e.g. "rs 10", "rs.9.50" or "re 1".
+///
+/// The marker has to start a word and be followed by the number, so the "rs"
+/// in "shareholders" or the "re" in "record" are not mistaken for one.
+fn rupee_amount(s: &str) -> Option<f64> {
+    const MARKERS: [&str; 4] = ["rs", "re", "inr", "₹"];
+
+    s.char_indices().find_map(|(i, _)| {
+        if s[..i].chars().next_back().is_some_and(|c| c.is_alphanumeric()) {
+            return None;
+        }
+        let marker = MARKERS.iter().find(|m| s[i..].starts_with(*m))?;
+        let rest = s[i + marker.len()..].trim_start_matches(['.', ' ']);
+        if !rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        numbers_in(rest).first().copied()
+    })
+}
+
+/// First `a:b` ratio in `s`
+fn ratio_in(s: &str) -> Option<(f64, f64)> {
+    let colon = s.find(':')?;
+    let new = numbers_in(&s[..colon]).last().copied()?;
+    let held = numbers_in(&s[colon + 1..]).first().copied()?;
+    Some((new, held))
+}
@@ -0,0 +1,143 @@
+// src/models/deals.rs
+use serde::{Deserialize, Deserializer, Serialize};
+use chrono::NaiveDate;
+use crate::{dates, numbers};
+use super::{DailyData, HistoricalData};
+
+/// Side of a bulk or block deal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DealSide {
+    Buy,
+    Sell,
+}
+
+/// A bulk or block deal.
+///
+/// Field names are those of the historical endpoints; the live snapshot's
+/// names are accepted as aliases.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deal {
+    #[serde(rename = "BD_DT_DATE", alias = "date", with = "dates::nse_date")]
+    pub date: NaiveDate,
+    #[serde(rename = "BD_SYMBOL", alias = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "BD_SCRIP_NAME", alias = "name", default)]
+    pub security_name: Option<String>,
+    #[serde(rename = "BD_CLIENT_NAME", alias = "clientName")]
+    pub client_name: String,
+    #[serde(rename = "BD_BUY_SELL", alias = "buySell")]
+    pub side: DealSide,
+    #[serde(rename = "BD_QTY_TRD", alias = "qty", with = "numbers::required")]
+    pub quantity: u64,
+    /// Weighted average trade price
+    #[serde(rename = "BD_TP_WATP", alias = "watp", with = "numbers::required")]
+    pub price: f64,
+    #[serde(rename = "BD_REMARKS", alias = "remarks", default)]
+    pub remarks: Option<String>,
+}
+
+/// Short selling reported for one security on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortSale {
+    #[serde(rename = "SS_DATE", alias = "date", with = "dates::nse_date")]
+    pub date: NaiveDate,
+    #[serde(rename = "SS_SYMBOL", alias = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "SS_NAME", alias = "name", default)]
+    pub security_name: Option<String>,
+    #[serde(rename = "SS_QTY", alias = "qty", with = "numbers::required")]
+    pub quantity: u64,
+}
+
+/// Response of NSE's `snapshot-capital-market-largedeal` endpoint: today's deals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeDeals {
+    #[serde(rename = "as_on_date", default, with = "dates::option_nse_date")]
+    pub as_on_date: Option<NaiveDate>,
+    #[serde(rename = "BULK_DEALS_DATA", default)]
+    pub bulk_deals: Vec<Deal>,
+    #[serde(rename = "BLOCK_DEALS_DATA", default)]
+    pub block_deals: Vec<Deal>,
+    #[serde(rename = "SHORT_DEALS_DATA", default)]
+    pub short_selling: Vec<ShortSale>,
+}
+
+/// Response of NSE's historical bulk and block deal endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DealRecords {
+    #[serde(rename = "data", default)]
+    pub data: Vec<Deal>,
+}
+
+/// Response of NSE's historical short selling endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortSellingRecords {
+    #[serde(rename = "data", default)]
+    pub data: Vec<ShortSale>,
+}
+
+/// A day of a symbol's history with the deals done in it
+#[derive(Debug, Clone)]
+pub struct DealDay<'a> {
+    pub day: &'a DailyData,
+    pub deals: Vec<&'a Deal>,
+}
+
+impl<'de> Deserialize<'de> for DealSide {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        match raw.trim().to_ascii_uppercase().as_str() {
+            "BUY" | "B" => Ok(DealSide::Buy),
+            "SELL" | "S" => Ok(DealSide::Sell),
+            _ => Err(serde::de::Error::custom(format!("unrecognised deal side: {}", raw))),
+        }
+    }
+}
+
+impl Deal {
+    pub fn value(&self) -> f64 {
+        self.quantity as f64 * self.price
+    }
+}
+
+impl DealDay<'_> {
+    /// Quantity bought in the day's deals
+    pub fn bought_quantity(&self) -> u64 {
+        self.quantity_on(DealSide::Buy)
+    }
+
+    pub fn sold_quantity(&self) -> u64 {
+        self.quantity_on(DealSide::Sell)
+    }
+
+    /// Share of the day's volume that went through deals, in percent.
+    ///
+    /// Both sides of a deal are reported, so this counts the larger side
+    /// rather than the sum.
+    pub fn volume_share(&self) -> Option<f64> {
+        let quantity = self.bought_quantity().max(self.sold_quantity());
+        (self.day.volume > 0).then(|| quantity as f64 / self.day.volume as f64 * 100.0)
+    }
+
+    fn quantity_on(&self, side: DealSide) -> u64 {
+        self.deals.iter().filter(|deal| deal.side == side).map(|deal| deal.quantity).sum()
+    }
+}
+
+impl HistoricalData {
+    /// Days of the history on which the symbol had deals, in date order.
+    ///
+    /// Deals in other symbols or on days outside the history are ignored.
+    pub fn join_deals<'a>(&'a self, deals: &'a [Deal]) -> Vec<DealDay<'a>> {
+        self.data
+            .iter()
+            .filter_map(|day| {
+                let day_deals: Vec<&Deal> = deals
+                    .iter()
+                    .filter(|deal| deal.date == day.timestamp && deal.symbol.eq_ignore_ascii_case(&self.symbol))
+                    .collect();
+                (!day_deals.is_empty()).then_some(DealDay { day, deals: day_deals })
+            })
+            .collect()
+    }
+}
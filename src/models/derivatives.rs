@@ -0,0 +1,286 @@
+// src/models/derivatives.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use crate::{dates, numbers};
+use crate::pricing::OptionType;
+
+/// Response of NSE's `quote-derivative` endpoint: every futures and
+/// options contract on one underlying
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivativeQuote {
+    #[serde(rename = "info")]
+    pub info: DerivativeInfo,
+    #[serde(rename = "underlyingValue", with = "numbers::required")]
+    pub underlying_value: f64,
+    #[serde(rename = "fut_timestamp", default, with = "dates::option_ist_datetime")]
+    pub fut_timestamp: Option<DateTime<FixedOffset>>,
+    #[serde(rename = "opt_timestamp", default, with = "dates::option_ist_datetime")]
+    pub opt_timestamp: Option<DateTime<FixedOffset>>,
+    #[serde(rename = "stocks")]
+    pub stocks: Vec<DerivativeContract>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivativeInfo {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "companyName", default)]
+    pub company_name: Option<String>,
+    #[serde(rename = "industry", default)]
+    pub industry: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivativeContract {
+    #[serde(rename = "metadata")]
+    pub metadata: ContractMetadata,
+    #[serde(rename = "marketDeptOrderBook", default)]
+    pub market_depth: Option<ContractMarketDepth>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractMetadata {
+    /// `FUTSTK`, `FUTIDX`, `OPTSTK` or `OPTIDX`
+    #[serde(rename = "instrumentType")]
+    pub instrument_type: String,
+    #[serde(rename = "expiryDate", with = "dates::nse_date")]
+    pub expiry_date: NaiveDate,
+    /// `Call`, `Put` or `-` for futures
+    #[serde(rename = "optionType")]
+    pub option_type: String,
+    #[serde(rename = "strikePrice", with = "numbers::required")]
+    pub strike_price: f64,
+    #[serde(rename = "identifier")]
+    pub identifier: String,
+    #[serde(rename = "openPrice", default, with = "numbers::option")]
+    pub open_price: Option<f64>,
+    #[serde(rename = "highPrice", default, with = "numbers::option")]
+    pub high_price: Option<f64>,
+    #[serde(rename = "lowPrice", default, with = "numbers::option")]
+    pub low_price: Option<f64>,
+    #[serde(rename = "closePrice", default, with = "numbers::option")]
+    pub close_price: Option<f64>,
+    #[serde(rename = "prevClose", default, with = "numbers::option")]
+    pub prev_close: Option<f64>,
+    #[serde(rename = "lastPrice", with = "numbers::required")]
+    pub last_price: f64,
+    #[serde(rename = "change", default, with = "numbers::option")]
+    pub change: Option<f64>,
+    #[serde(rename = "pChange", default, with = "numbers::option")]
+    pub p_change: Option<f64>,
+    #[serde(rename = "numberOfContractsTraded", default, with = "numbers::option")]
+    pub number_of_contracts_traded: Option<u64>,
+    #[serde(rename = "totalTurnover", default, with = "numbers::option")]
+    pub total_turnover: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractMarketDepth {
+    #[serde(rename = "totalBuyQuantity", default, with = "numbers::option")]
+    pub total_buy_quantity: Option<u64>,
+    #[serde(rename = "totalSellQuantity", default, with = "numbers::option")]
+    pub total_sell_quantity: Option<u64>,
+    #[serde(rename = "tradeInfo", default)]
+    pub trade_info: Option<ContractTradeInfo>,
+    #[serde(rename = "otherInfo", default)]
+    pub other_info: Option<ContractOtherInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractTradeInfo {
+    #[serde(rename = "tradedVolume", default, with = "numbers::option")]
+    pub traded_volume: Option<u64>,
+    #[serde(rename = "value", default, with = "numbers::option")]
+    pub value: Option<f64>,
+    #[serde(rename = "vmap", default, with = "numbers::option")]
+    pub vwap: Option<f64>,
+    #[serde(rename = "premiumTurnover", default, with = "numbers::option")]
+    pub premium_turnover: Option<f64>,
+    #[serde(rename = "openInterest", default, with = "numbers::option")]
+    pub open_interest: Option<f64>,
+    #[serde(rename = "changeinOpenInterest", default, with = "numbers::option")]
+    pub change_in_open_interest: Option<f64>,
+    #[serde(rename = "pchangeinOpenInterest", default, with = "numbers::option")]
+    pub p_change_in_open_interest: Option<f64>,
+    #[serde(rename = "marketLot", default, with = "numbers::option")]
+    pub market_lot: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractOtherInfo {
+    #[serde(rename = "settlementPrice", default, with = "numbers::option")]
+    pub settlement_price: Option<f64>,
+    #[serde(rename = "dailyvolatility", default, with = "numbers::option")]
+    pub daily_volatility: Option<f64>,
+    #[serde(rename = "annualisedVolatility", default, with = "numbers::option")]
+    pub annualised_volatility: Option<f64>,
+    #[serde(rename = "impliedVolatility", default, with = "numbers::option")]
+    pub implied_volatility: Option<f64>,
+    #[serde(rename = "clientWisePositionLimits", default, with = "numbers::option")]
+    pub client_wise_position_limits: Option<f64>,
+    #[serde(rename = "marketWidePositionLimits", default, with = "numbers::option")]
+    pub market_wide_position_limits: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContractKind {
+    StockFuture,
+    IndexFuture,
+    StockOption,
+    IndexOption,
+}
+
+/// A futures contract with its basis and implied carry against spot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuturesContract {
+    pub kind: ContractKind,
+    #[serde(with = "dates::nse_date")]
+    pub expiry_date: NaiveDate,
+    pub last_price: f64,
+    pub lot_size: Option<u32>,
+    pub open_interest: Option<f64>,
+    pub change_in_open_interest: Option<f64>,
+    pub volume: Option<u64>,
+    /// Futures price minus spot
+    pub basis: f64,
+    /// Annualised, continuously compounded carry implied by the basis
+    pub cost_of_carry: Option<f64>,
+}
+
+/// An options contract from the derivative quote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionContract {
+    pub kind: ContractKind,
+    #[serde(with = "dates::nse_date")]
+    pub expiry_date: NaiveDate,
+    pub option_type: OptionType,
+    pub strike_price: f64,
+    pub last_price: f64,
+    pub lot_size: Option<u32>,
+    pub open_interest: Option<f64>,
+    pub change_in_open_interest: Option<f64>,
+    pub volume: Option<u64>,
+    pub implied_volatility: Option<f64>,
+}
+
+impl DerivativeContract {
+    pub fn kind(&self) -> Option<ContractKind> {
+        match self.metadata.instrument_type.as_str() {
+            "FUTSTK" | "Stock Futures" => Some(ContractKind::StockFuture),
+            "FUTIDX" | "Index Futures" => Some(ContractKind::IndexFuture),
+            "OPTSTK" | "Stock Options" => Some(ContractKind::StockOption),
+            "OPTIDX" | "Index Options" => Some(ContractKind::IndexOption),
+            _ => None,
+        }
+    }
+
+    fn trade_info(&self) -> Option<&ContractTradeInfo> {
+        self.market_depth.as_ref().and_then(|d| d.trade_info.as_ref())
+    }
+
+    pub fn lot_size(&self) -> Option<u32> {
+        self.trade_info().and_then(|t| t.market_lot)
+    }
+
+    pub fn open_interest(&self) -> Option<f64> {
+        self.trade_info().and_then(|t| t.open_interest)
+    }
+
+    pub fn change_in_open_interest(&self) -> Option<f64> {
+        self.trade_info().and_then(|t| t.change_in_open_interest)
+    }
+
+    pub fn volume(&self) -> Option<u64> {
+        self.trade_info()
+            .and_then(|t| t.traded_volume)
+            .or(self.metadata.number_of_contracts_traded)
+    }
+}
+
+impl DerivativeQuote {
+    /// Futures contracts ordered by expiry, with basis and carry computed
+    /// against the underlying value as of `as_of` (normally today in IST)
+    pub fn futures(&self, as_of: NaiveDate) -> Vec<FuturesContract> {
+        let spot = self.underlying_value;
+
+        let mut futures: Vec<FuturesContract> = self.stocks
+            .iter()
+            .filter_map(|contract| {
+                let kind = contract.kind()?;
+                if !matches!(kind, ContractKind::StockFuture | ContractKind::IndexFuture) {
+                    return None;
+                }
+
+                let meta = &contract.metadata;
+                Some(FuturesContract {
+                    kind,
+                    expiry_date: meta.expiry_date,
+                    last_price: meta.last_price,
+                    lot_size: contract.lot_size(),
+                    open_interest: contract.open_interest(),
+                    change_in_open_interest: contract.change_in_open_interest(),
+                    volume: contract.volume(),
+                    basis: meta.last_price - spot,
+                    cost_of_carry: cost_of_carry(spot, meta.last_price, as_of, meta.expiry_date),
+                })
+            })
+            .collect();
+
+        futures.sort_by_key(|f| f.expiry_date);
+        futures
+    }
+
+    /// Options contracts ordered by expiry, strike and type
+    pub fn options(&self) -> Vec<OptionContract> {
+        let mut options: Vec<OptionContract> = self.stocks
+            .iter()
+            .filter_map(|contract| {
+                let kind = contract.kind()?;
+                if !matches!(kind, ContractKind::StockOption | ContractKind::IndexOption) {
+                    return None;
+                }
+
+                let meta = &contract.metadata;
+                let option_type = match meta.option_type.to_ascii_lowercase().as_str() {
+                    "call" | "ce" => OptionType::Call,
+                    "put" | "pe" => OptionType::Put,
+                    _ => return None,
+                };
+
+                Some(OptionContract {
+                    kind,
+                    expiry_date: meta.expiry_date,
+                    option_type,
+                    strike_price: meta.strike_price,
+                    last_price: meta.last_price,
+                    lot_size: contract.lot_size(),
+                    open_interest: contract.open_interest(),
+                    change_in_open_interest: contract.change_in_open_interest(),
+                    volume: contract.volume(),
+                    implied_volatility: contract.market_depth
+                        .as_ref()
+                        .and_then(|d| d.other_info.as_ref())
+                        .and_then(|o| o.implied_volatility),
+                })
+            })
+            .collect();
+
+        options.sort_by(|a, b| {
+            a.expiry_date
+                .cmp(&b.expiry_date)
+                .then(a.strike_price.total_cmp(&b.strike_price))
+                .then(a.option_type.cmp(&b.option_type))
+        });
+        options
+    }
+}
+
+/// Annualised carry `ln(F / S) / t`, with `t` in calendar years
+pub fn cost_of_carry(spot: f64, futures_price: f64, as_of: NaiveDate, expiry: NaiveDate) -> Option<f64> {
+    let days = (expiry - as_of).num_days();
+    if spot <= 0.0 || futures_price <= 0.0 || days <= 0 {
+        return None;
+    }
+
+    Some((futures_price / spot).ln() / (days as f64 / 365.0))
+}
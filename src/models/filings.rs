@@ -0,0 +1,312 @@
+// src/models/filings.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use crate::dates;
+use crate::symbol::Symbol;
+
+/// A corporate announcement from NSE's `corporate-announcements` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Announcement {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "sm_name", default)]
+    pub company: Option<String>,
+    /// Subject line, e.g. "Outcome of Board Meeting"
+    #[serde(rename = "desc")]
+    pub subject: String,
+    #[serde(rename = "attchmntText", default)]
+    pub details: Option<String>,
+    #[serde(rename = "attchmntFile", default)]
+    pub attachment_url: Option<String>,
+    #[serde(rename = "an_dt", default, with = "dates::option_ist_datetime")]
+    pub broadcast_time: Option<DateTime<FixedOffset>>,
+    #[serde(rename = "sm_isin", default)]
+    pub isin: Option<String>,
+    #[serde(rename = "smIndustry", default)]
+    pub industry: Option<String>,
+}
+
+/// A board meeting from NSE's `corporate-board-meetings` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardMeeting {
+    #[serde(rename = "bm_symbol")]
+    pub symbol: String,
+    #[serde(rename = "sm_name", default)]
+    pub company: Option<String>,
+    #[serde(rename = "bm_date", default, with = "dates::option_nse_date")]
+    pub meeting_date: Option<NaiveDate>,
+    #[serde(rename = "bm_purpose", default)]
+    pub purpose: String,
+    #[serde(rename = "bm_desc", default)]
+    pub description: Option<String>,
+    /// When the meeting was announced
+    #[serde(rename = "bm_timestamp", default, with = "dates::option_ist_datetime")]
+    pub announced_at: Option<DateTime<FixedOffset>>,
+    #[serde(rename = "attachment", default)]
+    pub attachment_url: Option<String>,
+    #[serde(rename = "sm_isin", default)]
+    pub isin: Option<String>,
+}
+
+/// A filed financial result from NSE's `corporates-financial-results` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinancialResult {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "companyName", default)]
+    pub company: Option<String>,
+    /// "Audited" or "Un-Audited"
+    #[serde(rename = "audited", default)]
+    pub audited: Option<String>,
+    /// "Consolidated", "Non-Consolidated" or "Standalone"
+    #[serde(rename = "consolidated", default)]
+    pub consolidated: Option<String>,
+    #[serde(rename = "period", default)]
+    pub period: Option<String>,
+    /// First day of the period the result covers
+    #[serde(rename = "fromDate", default, with = "dates::option_nse_date")]
+    pub period_start: Option<NaiveDate>,
+    #[serde(rename = "toDate", default, with = "dates::option_nse_date")]
+    pub period_end: Option<NaiveDate>,
+    #[serde(rename = "filingDate", default, with = "dates::option_ist_datetime")]
+    pub filing_time: Option<DateTime<FixedOffset>>,
+    #[serde(rename = "broadCastDate", default, with = "dates::option_ist_datetime")]
+    pub broadcast_time: Option<DateTime<FixedOffset>>,
+    #[serde(rename = "xbrl", default)]
+    pub xbrl_url: Option<String>,
+    #[serde(rename = "isin", default)]
+    pub isin: Option<String>,
+}
+
+/// What an announcement or board meeting is about, parsed from its subject or purpose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilingCategory {
+    FinancialResults,
+    BoardMeeting,
+    Dividend,
+    Bonus,
+    Split,
+    Buyback,
+    FundRaising,
+    /// AGM, EGM or postal ballot
+    ShareholderMeeting,
+    AnalystMeet,
+    CreditRating,
+    Acquisition,
+    ManagementChange,
+    PressRelease,
+    Other,
+}
+
+/// Reporting periods of financial results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultPeriod {
+    Quarterly,
+    HalfYearly,
+    Annual,
+    Others,
+}
+
+/// Symbol and date range for the filing endpoints.
+///
+/// Without dates NSE returns its most recent filings.
+#[derive(Debug, Clone, Default)]
+pub struct FilingFilter {
+    pub symbol: Option<Symbol>,
+    pub from_date: Option<NaiveDate>,
+    pub to_date: Option<NaiveDate>,
+}
+
+/// Something with a broadcast time that a [`FilingCursor`] can track
+pub trait Filing {
+    fn filed_at(&self) -> Option<DateTime<FixedOffset>>;
+
+    /// Distinguishes filings broadcast in the same second
+    fn filing_key(&self) -> String;
+}
+
+/// Position in a filing feed, for polling only what is new.
+///
+/// Serializable so that it can be persisted between runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FilingCursor {
+    #[serde(default, with = "dates::option_ist_datetime")]
+    last_seen: Option<DateTime<FixedOffset>>,
+    /// Keys of the filings broadcast at `last_seen`
+    #[serde(default)]
+    keys_at_last_seen: Vec<String>,
+}
+
+const CATEGORY_KEYWORDS: [(&str, FilingCategory); 17] = [
+    ("financial result", FilingCategory::FinancialResults),
+    ("dividend", FilingCategory::Dividend),
+    ("bonus", FilingCategory::Bonus),
+    ("sub-division", FilingCategory::Split),
+    ("split", FilingCategory::Split),
+    ("buy back", FilingCategory::Buyback),
+    ("buyback", FilingCategory::Buyback),
+    ("fund raising", FilingCategory::FundRaising),
+    ("preferential issue", FilingCategory::FundRaising),
+    ("analyst", FilingCategory::AnalystMeet),
+    ("credit rating", FilingCategory::CreditRating),
+    ("acquisition", FilingCategory::Acquisition),
+    ("change in director", FilingCategory::ManagementChange),
+    ("resignation", FilingCategory::ManagementChange),
+    ("shareholders meeting", FilingCategory::ShareholderMeeting),
+    ("postal ballot", FilingCategory::ShareholderMeeting),
+    ("press release", FilingCategory::PressRelease),
+];
+
+impl FilingCategory {
+    /// Category of a subject or purpose line; the first matching keyword wins
+    pub fn from_text(text: &str) -> FilingCategory {
+        let text = text.to_ascii_lowercase();
+        CATEGORY_KEYWORDS
+            .iter()
+            .find(|(keyword, _)| text.contains(keyword))
+            .map(|(_, category)| *category)
+            .unwrap_or_else(|| {
+                if text.contains("board meeting") {
+                    FilingCategory::BoardMeeting
+                } else {
+                    FilingCategory::Other
+                }
+            })
+    }
+}
+
+impl ResultPeriod {
+    /// Value of the endpoint's `period` parameter
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            ResultPeriod::Quarterly => "Quarterly",
+            ResultPeriod::HalfYearly => "Half-Yearly",
+            ResultPeriod::Annual => "Annual",
+            ResultPeriod::Others => "Others",
+        }
+    }
+}
+
+impl Announcement {
+    pub fn category(&self) -> FilingCategory {
+        FilingCategory::from_text(&self.subject)
+    }
+}
+
+impl BoardMeeting {
+    /// Category of the meeting's purpose, e.g. results or a dividend
+    pub fn category(&self) -> FilingCategory {
+        FilingCategory::from_text(&self.purpose)
+    }
+}
+
+impl FinancialResult {
+    pub fn is_audited(&self) -> bool {
+        self.audited.as_deref().is_some_and(|a| a.eq_ignore_ascii_case("audited"))
+    }
+
+    pub fn is_consolidated(&self) -> bool {
+        self.consolidated.as_deref().is_some_and(|c| c.eq_ignore_ascii_case("consolidated"))
+    }
+}
+
+impl FilingFilter {
+    pub fn symbol(mut self, symbol: Symbol) -> Self {
+        self.symbol = Some(symbol);
+        self
+    }
+
+    pub fn date_range(mut self, from_date: NaiveDate, to_date: NaiveDate) -> Self {
+        self.from_date = Some(from_date);
+        self.to_date = Some(to_date);
+        self
+    }
+}
+
+impl Filing for Announcement {
+    fn filed_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.broadcast_time
+    }
+
+    fn filing_key(&self) -> String {
+        format!("{}|{}|{}", self.symbol, self.subject, self.attachment_url.as_deref().unwrap_or_default())
+    }
+}
+
+impl Filing for BoardMeeting {
+    fn filed_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.announced_at
+    }
+
+    fn filing_key(&self) -> String {
+        format!("{}|{}|{:?}", self.symbol, self.purpose, self.meeting_date)
+    }
+}
+
+impl Filing for FinancialResult {
+    fn filed_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.broadcast_time.or(self.filing_time)
+    }
+
+    fn filing_key(&self) -> String {
+        format!(
+            "{}|{:?}|{:?}|{}",
+            self.symbol,
+            self.period_end,
+            self.consolidated,
+            self.xbrl_url.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+impl FilingCursor {
+    /// A cursor that treats every filing as new
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cursor that treats filings after `time` as new
+    pub fn starting_at(time: DateTime<FixedOffset>) -> Self {
+        FilingCursor {
+            last_seen: Some(time),
+            keys_at_last_seen: Vec::new(),
+        }
+    }
+
+    /// Broadcast time of the newest filing seen so far
+    pub fn last_seen(&self) -> Option<DateTime<FixedOffset>> {
+        self.last_seen
+    }
+
+    pub fn is_new<F: Filing>(&self, filing: &F) -> bool {
+        let Some(last_seen) = self.last_seen else {
+            return true;
+        };
+        match filing.filed_at() {
+            Some(time) if time > last_seen => true,
+            Some(time) if time == last_seen => !self.keys_at_last_seen.contains(&filing.filing_key()),
+            _ => false,
+        }
+    }
+
+    /// Keep the filings not seen before, oldest first, and move the cursor past them.
+    ///
+    /// Once the cursor has a position, filings without a timestamp cannot be
+    /// placed and are dropped.
+    pub fn advance<F: Filing>(&mut self, filings: Vec<F>) -> Vec<F> {
+        let mut new: Vec<F> = filings.into_iter().filter(|f| self.is_new(f)).collect();
+        new.sort_by_key(|f| f.filed_at());
+
+        for filing in &new {
+            let Some(time) = filing.filed_at() else {
+                continue;
+            };
+            if self.last_seen != Some(time) {
+                self.last_seen = Some(time);
+                self.keys_at_last_seen.clear();
+            }
+            self.keys_at_last_seen.push(filing.filing_key());
+        }
+        new
+    }
+}
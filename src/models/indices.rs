@@ -0,0 +1,189 @@
+// src/models/indices.rs
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use crate::{dates, numbers};
+use super::{DailyData, HistoricalData};
+
+/// Response of NSE's `historical/indicesHistory` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexHistory {
+    #[serde(rename = "data")]
+    pub data: IndexHistoryRecords,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexHistoryRecords {
+    #[serde(rename = "indexCloseOnlineRecords", default)]
+    pub close_records: Vec<IndexCloseRecord>,
+    #[serde(rename = "indexTurnoverRecords", default)]
+    pub turnover_records: Vec<IndexTurnoverRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexCloseRecord {
+    #[serde(rename = "EOD_INDEX_NAME")]
+    pub index_name: String,
+    #[serde(rename = "EOD_TIMESTAMP", with = "dates::nse_date")]
+    pub date: NaiveDate,
+    #[serde(rename = "EOD_OPEN_INDEX_VAL", with = "numbers::required")]
+    pub open: f64,
+    #[serde(rename = "EOD_HIGH_INDEX_VAL", with = "numbers::required")]
+    pub high: f64,
+    #[serde(rename = "EOD_LOW_INDEX_VAL", with = "numbers::required")]
+    pub low: f64,
+    #[serde(rename = "EOD_CLOSE_INDEX_VAL", with = "numbers::required")]
+    pub close: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexTurnoverRecord {
+    #[serde(rename = "HIT_INDEX_NAME_UPPER", default)]
+    pub index_name: Option<String>,
+    #[serde(rename = "HIT_TIMESTAMP", with = "dates::nse_date")]
+    pub date: NaiveDate,
+    #[serde(rename = "HIT_TRADED_QTY", default, with = "numbers::option")]
+    pub traded_qty: Option<u64>,
+    #[serde(rename = "HIT_TURN_OVER", default, with = "numbers::option")]
+    pub turnover: Option<f64>,
+}
+
+impl IndexHistory {
+    /// Daily rows in the shape of an equity history, sorted by date.
+    ///
+    /// Volume and value come from the turnover records of the same day.
+    /// Indices have no previous close in this response, so it is taken
+    /// from the prior row (the first row uses its own open).
+    pub fn into_historical(self, index: &str) -> HistoricalData {
+        let turnover: HashMap<NaiveDate, IndexTurnoverRecord> = self.data.turnover_records
+            .into_iter()
+            .map(|record| (record.date, record))
+            .collect();
+
+        let mut records = self.data.close_records;
+        records.sort_by_key(|r| r.date);
+        records.dedup_by_key(|r| r.date);
+
+        let mut data = Vec::with_capacity(records.len());
+        let mut prev_close = None;
+        for record in records {
+            let day_turnover = turnover.get(&record.date);
+            data.push(DailyData {
+                timestamp: record.date,
+                open: record.open,
+                high: record.high,
+                low: record.low,
+                close: record.close,
+                last: record.close,
+                prev_close: prev_close.unwrap_or(record.open),
+                volume: day_turnover.and_then(|t| t.traded_qty).unwrap_or(0),
+                value: day_turnover.and_then(|t| t.turnover).unwrap_or(0.0),
+                ..Default::default()
+            });
+            prev_close = Some(record.close);
+        }
+
+        let mut history = HistoricalData {
+            symbol: index.to_string(),
+            data,
+        };
+        history.fill_year_range();
+        history
+    }
+}
+
+/// Response of NSE's `equity-stockIndices` endpoint: an index and its members
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexStocks {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "timestamp", default, with = "dates::option_ist_datetime")]
+    pub timestamp: Option<DateTime<FixedOffset>>,
+    /// The index itself (priority 1) followed by its constituents
+    #[serde(rename = "data")]
+    pub data: Vec<IndexStockRow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexStockRow {
+    /// 1 for the index's own row, 0 for constituents
+    #[serde(rename = "priority", default)]
+    pub priority: u8,
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "series", default)]
+    pub series: Option<String>,
+    #[serde(rename = "open", default, with = "numbers::option")]
+    pub open: Option<f64>,
+    #[serde(rename = "dayHigh", default, with = "numbers::option")]
+    pub day_high: Option<f64>,
+    #[serde(rename = "dayLow", default, with = "numbers::option")]
+    pub day_low: Option<f64>,
+    #[serde(rename = "lastPrice", with = "numbers::required")]
+    pub last_price: f64,
+    #[serde(rename = "previousClose", default, with = "numbers::option")]
+    pub previous_close: Option<f64>,
+    #[serde(rename = "change", default, with = "numbers::option")]
+    pub change: Option<f64>,
+    #[serde(rename = "pChange", default, with = "numbers::option")]
+    pub p_change: Option<f64>,
+    #[serde(rename = "ffmc", default, with = "numbers::option")]
+    pub ffmc: Option<f64>,
+    #[serde(rename = "yearHigh", default, with = "numbers::option")]
+    pub year_high: Option<f64>,
+    #[serde(rename = "yearLow", default, with = "numbers::option")]
+    pub year_low: Option<f64>,
+    #[serde(rename = "totalTradedVolume", default, with = "numbers::option")]
+    pub total_traded_volume: Option<u64>,
+    #[serde(rename = "totalTradedValue", default, with = "numbers::option")]
+    pub total_traded_value: Option<f64>,
+}
+
+/// A member of an index with its weight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexConstituent {
+    pub symbol: String,
+    pub series: Option<String>,
+    pub last_price: f64,
+    pub p_change: Option<f64>,
+    /// Free-float market cap as published by NSE
+    pub ffmc: Option<f64>,
+    /// Share of the index's free-float market cap, in percent
+    pub weight: Option<f64>,
+}
+
+impl IndexStocks {
+    /// Constituents ordered by weight, heaviest first.
+    ///
+    /// NSE does not publish weights here; they are each member's share of
+    /// the summed free-float market cap, which is how the free-float indices
+    /// are weighted (before any capping).
+    pub fn constituents(&self) -> Vec<IndexConstituent> {
+        let members: Vec<&IndexStockRow> = self.data
+            .iter()
+            .filter(|row| row.priority == 0 && row.symbol != self.name)
+            .collect();
+        let total_ffmc: f64 = members.iter().filter_map(|row| row.ffmc).sum();
+
+        let mut constituents: Vec<IndexConstituent> = members
+            .into_iter()
+            .map(|row| IndexConstituent {
+                symbol: row.symbol.clone(),
+                series: row.series.clone(),
+                last_price: row.last_price,
+                p_change: row.p_change,
+                ffmc: row.ffmc,
+                weight: row.ffmc.filter(|_| total_ffmc > 0.0).map(|ffmc| ffmc / total_ffmc * 100.0),
+            })
+            .collect();
+
+        constituents.sort_by(|a, b| {
+            b.weight
+                .unwrap_or(0.0)
+                .total_cmp(&a.weight.unwrap_or(0.0))
+                .then_with(|| a.symbol.cmp(&b.symbol))
+        });
+        constituents
+    }
+}
@@ -0,0 +1,227 @@
+// src/models/insider.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use crate::{dates, numbers};
+use super::Filing;
+
+/// A disclosure under the SEBI insider trading (PIT) regulations,
+/// from NSE's `corporates-pit` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsiderTrade {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "company", default)]
+    pub company: Option<String>,
+    /// Name of the acquirer or disposer
+    #[serde(rename = "acqName")]
+    pub person: String,
+    /// Category of person, e.g. "Promoter Group" or "Director"
+    #[serde(rename = "personCategory", default)]
+    pub person_category: Option<String>,
+    #[serde(rename = "secType", default)]
+    pub security_type: Option<String>,
+    /// Securities acquired or disposed of
+    #[serde(rename = "secAcq", default, with = "numbers::option")]
+    pub securities: Option<u64>,
+    #[serde(rename = "secVal", default, with = "numbers::option")]
+    pub value: Option<f64>,
+    /// "Buy", "Sell", "Pledge Created", ...
+    #[serde(rename = "tdpTransactionType", default)]
+    pub transaction_type: Option<String>,
+    #[serde(rename = "befAcqSharesNo", default, with = "numbers::option")]
+    pub shares_before: Option<u64>,
+    #[serde(rename = "befAcqSharesPer", default, with = "numbers::option")]
+    pub holding_pct_before: Option<f64>,
+    #[serde(rename = "afterAcqSharesNo", default, with = "numbers::option")]
+    pub shares_after: Option<u64>,
+    #[serde(rename = "afterAcqSharesPer", default, with = "numbers::option")]
+    pub holding_pct_after: Option<f64>,
+    /// "Market Purchase", "Off Market", "Inter-se-Transfer", ...
+    #[serde(rename = "acqMode", default)]
+    pub mode: Option<String>,
+    #[serde(rename = "acqfromDt", default, with = "dates::option_nse_date")]
+    pub from_date: Option<NaiveDate>,
+    #[serde(rename = "acqtoDt", default, with = "dates::option_nse_date")]
+    pub to_date: Option<NaiveDate>,
+    /// Date the company was informed
+    #[serde(rename = "intimDt", default, with = "dates::option_nse_date")]
+    pub intimation_date: Option<NaiveDate>,
+    /// When NSE disseminated the disclosure
+    #[serde(rename = "date", default, with = "dates::option_ist_datetime")]
+    pub disclosed_at: Option<DateTime<FixedOffset>>,
+    #[serde(rename = "xbrl", default)]
+    pub xbrl_url: Option<String>,
+}
+
+/// A disclosure under SEBI's takeover (SAST) regulations,
+/// from NSE's `corporate-sast-reg29` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SastDisclosure {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "company", default)]
+    pub company: Option<String>,
+    #[serde(rename = "acquirerName")]
+    pub acquirer: String,
+    /// "Promoter" or "Non-Promoter"
+    #[serde(rename = "promoterType", default)]
+    pub promoter_type: Option<String>,
+    /// Regulation the disclosure is made under, e.g. "29(1)"
+    #[serde(rename = "regType", default)]
+    pub regulation: Option<String>,
+    /// "Acquisition" or "Disposal"
+    #[serde(rename = "acqSaleType", default)]
+    pub transaction_type: Option<String>,
+    #[serde(rename = "noOfShareAcq", default, with = "numbers::option")]
+    pub shares_acquired: Option<u64>,
+    #[serde(rename = "noOfShareSale", default, with = "numbers::option")]
+    pub shares_sold: Option<u64>,
+    #[serde(rename = "noOfShareAft", default, with = "numbers::option")]
+    pub shares_after: Option<u64>,
+    /// Holding after the transaction, in percent of share capital
+    #[serde(rename = "totAcqShare", default, with = "numbers::option")]
+    pub holding_pct_after: Option<f64>,
+    #[serde(rename = "totAcqDiluted", default, with = "numbers::option")]
+    pub diluted_pct_after: Option<f64>,
+    #[serde(rename = "acquisitionMode", default)]
+    pub mode: Option<String>,
+    #[serde(rename = "acqDate", default, with = "dates::option_nse_date")]
+    pub transaction_date: Option<NaiveDate>,
+    #[serde(rename = "timestamp", default, with = "dates::option_ist_datetime")]
+    pub disclosed_at: Option<DateTime<FixedOffset>>,
+    #[serde(rename = "attachement", default)]
+    pub attachment_url: Option<String>,
+}
+
+/// Response of NSE's `corporates-pit` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsiderTrades {
+    #[serde(rename = "data", default)]
+    pub data: Vec<InsiderTrade>,
+}
+
+/// Response of NSE's `corporate-sast-reg29` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SastDisclosures {
+    #[serde(rename = "data", default)]
+    pub data: Vec<SastDisclosure>,
+}
+
+/// Category of the person making a disclosure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersonCategory {
+    Promoter,
+    PromoterGroup,
+    Director,
+    KeyManagerialPersonnel,
+    Employee,
+    ImmediateRelative,
+    Other,
+}
+
+/// What a disclosed transaction did to the holding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InsiderSide {
+    Acquisition,
+    Disposal,
+    PledgeCreated,
+    PledgeRevoked,
+    Other,
+}
+
+impl PersonCategory {
+    pub fn from_text(text: &str) -> PersonCategory {
+        let text = text.to_ascii_lowercase();
+        if text.contains("non-promoter") || text.contains("non promoter") {
+            PersonCategory::Other
+        } else if text.contains("promoter group") {
+            PersonCategory::PromoterGroup
+        } else if text.contains("promoter") {
+            PersonCategory::Promoter
+        } else if text.contains("director") {
+            PersonCategory::Director
+        } else if text.contains("key managerial") || text.contains("kmp") {
+            PersonCategory::KeyManagerialPersonnel
+        } else if text.contains("relative") {
+            PersonCategory::ImmediateRelative
+        } else if text.contains("employee") || text.contains("designated person") {
+            PersonCategory::Employee
+        } else {
+            PersonCategory::Other
+        }
+    }
+
+    pub fn is_promoter(&self) -> bool {
+        matches!(self, PersonCategory::Promoter | PersonCategory::PromoterGroup)
+    }
+}
+
+impl InsiderSide {
+    pub fn from_text(text: &str) -> InsiderSide {
+        let text = text.to_ascii_lowercase();
+        if text.contains("revok") || text.contains("release") {
+            InsiderSide::PledgeRevoked
+        } else if text.contains("pledge") {
+            InsiderSide::PledgeCreated
+        } else if text.contains("buy") || text.contains("acqui") || text.contains("purchase") {
+            InsiderSide::Acquisition
+        } else if text.contains("sell") || text.contains("sale") || text.contains("dispos") {
+            InsiderSide::Disposal
+        } else {
+            InsiderSide::Other
+        }
+    }
+}
+
+impl InsiderTrade {
+    pub fn category(&self) -> PersonCategory {
+        PersonCategory::from_text(self.person_category.as_deref().unwrap_or_default())
+    }
+
+    pub fn side(&self) -> InsiderSide {
+        InsiderSide::from_text(self.transaction_type.as_deref().unwrap_or_default())
+    }
+
+    /// Change in the person's holding, in percentage points
+    pub fn holding_change_pct(&self) -> Option<f64> {
+        Some(self.holding_pct_after? - self.holding_pct_before?)
+    }
+}
+
+impl SastDisclosure {
+    pub fn is_promoter(&self) -> bool {
+        self.promoter_type.as_deref().is_some_and(|t| t.trim().eq_ignore_ascii_case("promoter"))
+    }
+
+    /// The transaction type, or failing that whichever of acquired and sold shares is set
+    pub fn side(&self) -> InsiderSide {
+        match self.transaction_type.as_deref().map(InsiderSide::from_text) {
+            Some(side) if side != InsiderSide::Other => side,
+            _ => match (self.shares_acquired.unwrap_or(0), self.shares_sold.unwrap_or(0)) {
+                (acquired, 0) if acquired > 0 => InsiderSide::Acquisition,
+                (0, sold) if sold > 0 => InsiderSide::Disposal,
+                _ => InsiderSide::Other,
+            },
+        }
+    }
+}
+
+impl Filing for InsiderTrade {
+    fn filed_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.disclosed_at
+    }
+
+    fn filing_key(&self) -> String {
+        format!("{}|{}|{:?}|{:?}", self.symbol, self.person, self.securities, self.transaction_type)
+    }
+}
+
+impl Filing for SastDisclosure {
+    fn filed_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.disclosed_at
+    }
+
+    fn filing_key(&self) -> String {
+        format!("{}|{}|{:?}|{:?}", self.symbol, self.acquirer, self.shares_acquired, self.shares_sold)
+    }
+}
@@ -0,0 +1,347 @@
+// src/models/movers.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use crate::{dates, numbers};
+
+/// Stock groups NSE's gainers/losers lists are published for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoversIndex {
+    Nifty,
+    BankNifty,
+    NiftyNext50,
+    /// All securities priced above ₹20
+    SecuritiesAbove20,
+    /// All securities priced at or below ₹20
+    SecuritiesBelow20,
+    /// Stocks in the F&O segment
+    FnO,
+    All,
+}
+
+/// A stock on one of NSE's live-analysis lists, in the shape of [`PriceInfo`](super::PriceInfo).
+///
+/// Fields a list does not publish are `None`. `value` is always in rupees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoverRow {
+    pub symbol: String,
+    pub series: Option<String>,
+    pub last_price: f64,
+    pub change: Option<f64>,
+    pub p_change: Option<f64>,
+    pub previous_close: Option<f64>,
+    pub open: Option<f64>,
+    pub day_high: Option<f64>,
+    pub day_low: Option<f64>,
+    pub volume: Option<u64>,
+    pub value: Option<f64>,
+    pub year_high: Option<f64>,
+    pub year_low: Option<f64>,
+}
+
+/// Response of NSE's `live-analysis-variations` endpoint (gainers or losers)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Variations {
+    #[serde(rename = "NIFTY", default)]
+    pub nifty: Option<VariationGroup>,
+    #[serde(rename = "BANKNIFTY", default)]
+    pub bank_nifty: Option<VariationGroup>,
+    #[serde(rename = "NIFTYNEXT50", default)]
+    pub nifty_next_50: Option<VariationGroup>,
+    #[serde(rename = "SecGtr20", default)]
+    pub securities_above_20: Option<VariationGroup>,
+    #[serde(rename = "SecLwr20", default)]
+    pub securities_below_20: Option<VariationGroup>,
+    #[serde(rename = "FOSec", default)]
+    pub fno: Option<VariationGroup>,
+    #[serde(rename = "allSec", default)]
+    pub all: Option<VariationGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariationGroup {
+    #[serde(rename = "data", default)]
+    pub data: Vec<VariationRecord>,
+    #[serde(rename = "timestamp", default, with = "dates::option_ist_datetime")]
+    pub timestamp: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariationRecord {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "series", default)]
+    pub series: Option<String>,
+    #[serde(rename = "open_price", default, with = "numbers::option")]
+    pub open: Option<f64>,
+    #[serde(rename = "high_price", default, with = "numbers::option")]
+    pub high: Option<f64>,
+    #[serde(rename = "low_price", default, with = "numbers::option")]
+    pub low: Option<f64>,
+    #[serde(rename = "ltp", with = "numbers::required")]
+    pub ltp: f64,
+    #[serde(rename = "prev_price", default, with = "numbers::option")]
+    pub prev_price: Option<f64>,
+    #[serde(rename = "perChange", default, with = "numbers::option")]
+    pub per_change: Option<f64>,
+    #[serde(rename = "trade_quantity", default, with = "numbers::option")]
+    pub trade_quantity: Option<u64>,
+    /// In ₹ lakhs
+    #[serde(rename = "turnover", default, with = "numbers::option")]
+    pub turnover: Option<f64>,
+}
+
+/// Response of NSE's `live-analysis-most-active-securities` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MostActive {
+    #[serde(rename = "data", default)]
+    pub data: Vec<MostActiveRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MostActiveRecord {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "lastPrice", with = "numbers::required")]
+    pub last_price: f64,
+    #[serde(rename = "change", default, with = "numbers::option")]
+    pub change: Option<f64>,
+    #[serde(rename = "pChange", default, with = "numbers::option")]
+    pub p_change: Option<f64>,
+    #[serde(rename = "previousClose", default, with = "numbers::option")]
+    pub previous_close: Option<f64>,
+    #[serde(rename = "open", default, with = "numbers::option")]
+    pub open: Option<f64>,
+    #[serde(rename = "dayHigh", default, with = "numbers::option")]
+    pub day_high: Option<f64>,
+    #[serde(rename = "dayLow", default, with = "numbers::option")]
+    pub day_low: Option<f64>,
+    #[serde(rename = "totalTradedVolume", default, with = "numbers::option")]
+    pub total_traded_volume: Option<u64>,
+    #[serde(rename = "totalTradedValue", default, with = "numbers::option")]
+    pub total_traded_value: Option<f64>,
+    #[serde(rename = "yearHigh", default, with = "numbers::option")]
+    pub year_high: Option<f64>,
+    #[serde(rename = "yearLow", default, with = "numbers::option")]
+    pub year_low: Option<f64>,
+}
+
+/// Response of NSE's 52-week high and low stock lists
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearExtremes {
+    #[serde(rename = "data", default)]
+    pub data: Vec<YearExtremeRecord>,
+    #[serde(rename = "timestamp", default, with = "dates::option_ist_datetime")]
+    pub timestamp: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearExtremeRecord {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "series", default)]
+    pub series: Option<String>,
+    /// The new 52-week high or low
+    #[serde(rename = "new52WHL", default, with = "numbers::option")]
+    pub new_extreme: Option<f64>,
+    #[serde(rename = "prev52WHL", default, with = "numbers::option")]
+    pub prev_extreme: Option<f64>,
+    #[serde(rename = "prevHLDate", default, with = "dates::option_nse_date")]
+    pub prev_extreme_date: Option<NaiveDate>,
+    #[serde(rename = "ltp", with = "numbers::required")]
+    pub ltp: f64,
+    #[serde(rename = "prevClose", default, with = "numbers::option")]
+    pub prev_close: Option<f64>,
+    #[serde(rename = "change", default, with = "numbers::option")]
+    pub change: Option<f64>,
+    #[serde(rename = "pChange", default, with = "numbers::option")]
+    pub p_change: Option<f64>,
+}
+
+/// Response of NSE's `live-analysis-price-band-hitter` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceBandHitters {
+    #[serde(rename = "upper", default)]
+    pub upper: PriceBandGroups,
+    #[serde(rename = "lower", default)]
+    pub lower: PriceBandGroups,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PriceBandGroups {
+    #[serde(rename = "AllSec", default)]
+    pub all: Option<PriceBandGroup>,
+    #[serde(rename = "SecGtr20", default)]
+    pub securities_above_20: Option<PriceBandGroup>,
+    #[serde(rename = "SecLwr20", default)]
+    pub securities_below_20: Option<PriceBandGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceBandGroup {
+    #[serde(rename = "data", default)]
+    pub data: Vec<PriceBandRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceBandRecord {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "series", default)]
+    pub series: Option<String>,
+    #[serde(rename = "ltp", with = "numbers::required")]
+    pub ltp: f64,
+    #[serde(rename = "change", default, with = "numbers::option")]
+    pub change: Option<f64>,
+    #[serde(rename = "pChange", default, with = "numbers::option")]
+    pub p_change: Option<f64>,
+    #[serde(rename = "upperCP", default, with = "numbers::option")]
+    pub upper_cp: Option<f64>,
+    #[serde(rename = "lowerCP", default, with = "numbers::option")]
+    pub lower_cp: Option<f64>,
+    /// Band width in percent, `None` for "No Band"
+    #[serde(rename = "priceBand", default, with = "numbers::option")]
+    pub price_band: Option<f64>,
+    #[serde(rename = "totalTradedVol", default, with = "numbers::option")]
+    pub total_traded_vol: Option<u64>,
+    /// In ₹ lakhs
+    #[serde(rename = "turnover", default, with = "numbers::option")]
+    pub turnover: Option<f64>,
+    #[serde(rename = "yearHigh", default, with = "numbers::option")]
+    pub year_high: Option<f64>,
+    #[serde(rename = "yearLow", default, with = "numbers::option")]
+    pub year_low: Option<f64>,
+}
+
+/// Every live-analysis list at once, for a market overview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketMovers {
+    pub gainers: Vec<MoverRow>,
+    pub losers: Vec<MoverRow>,
+    pub most_active_by_volume: Vec<MoverRow>,
+    pub most_active_by_value: Vec<MoverRow>,
+    pub year_highs: Vec<MoverRow>,
+    pub year_lows: Vec<MoverRow>,
+    pub upper_circuit: Vec<MoverRow>,
+    pub lower_circuit: Vec<MoverRow>,
+}
+
+impl Variations {
+    /// Rows of one index, empty when NSE sent no group for it
+    pub fn rows(&self, index: MoversIndex) -> Vec<MoverRow> {
+        let group = match index {
+            MoversIndex::Nifty => &self.nifty,
+            MoversIndex::BankNifty => &self.bank_nifty,
+            MoversIndex::NiftyNext50 => &self.nifty_next_50,
+            MoversIndex::SecuritiesAbove20 => &self.securities_above_20,
+            MoversIndex::SecuritiesBelow20 => &self.securities_below_20,
+            MoversIndex::FnO => &self.fno,
+            MoversIndex::All => &self.all,
+        };
+        group.iter().flat_map(|g| &g.data).map(MoverRow::from).collect()
+    }
+}
+
+impl MostActive {
+    pub fn rows(&self) -> Vec<MoverRow> {
+        self.data.iter().map(MoverRow::from).collect()
+    }
+}
+
+impl YearExtremes {
+    /// Rows of the 52-week high list, with the new high as `year_high`
+    pub fn highs(&self) -> Vec<MoverRow> {
+        self.data.iter().map(|r| MoverRow { year_high: r.new_extreme, ..r.to_row() }).collect()
+    }
+
+    /// Rows of the 52-week low list, with the new low as `year_low`
+    pub fn lows(&self) -> Vec<MoverRow> {
+        self.data.iter().map(|r| MoverRow { year_low: r.new_extreme, ..r.to_row() }).collect()
+    }
+}
+
+impl YearExtremeRecord {
+    fn to_row(&self) -> MoverRow {
+        MoverRow {
+            symbol: self.symbol.clone(),
+            series: self.series.clone(),
+            last_price: self.ltp,
+            change: self.change,
+            p_change: self.p_change,
+            previous_close: self.prev_close,
+            open: None,
+            day_high: None,
+            day_low: None,
+            volume: None,
+            value: None,
+            year_high: None,
+            year_low: None,
+        }
+    }
+}
+
+impl PriceBandGroups {
+    /// Rows across all securities
+    pub fn rows(&self) -> Vec<MoverRow> {
+        self.all.iter().flat_map(|g| &g.data).map(MoverRow::from).collect()
+    }
+}
+
+impl From<&VariationRecord> for MoverRow {
+    fn from(r: &VariationRecord) -> Self {
+        MoverRow {
+            symbol: r.symbol.clone(),
+            series: r.series.clone(),
+            last_price: r.ltp,
+            change: r.prev_price.map(|prev| r.ltp - prev),
+            p_change: r.per_change,
+            previous_close: r.prev_price,
+            open: r.open,
+            day_high: r.high,
+            day_low: r.low,
+            volume: r.trade_quantity,
+            value: r.turnover.map(|t| t * numbers::RUPEES_PER_LAKH),
+            year_high: None,
+            year_low: None,
+        }
+    }
+}
+
+impl From<&MostActiveRecord> for MoverRow {
+    fn from(r: &MostActiveRecord) -> Self {
+        MoverRow {
+            symbol: r.symbol.clone(),
+            series: None,
+            last_price: r.last_price,
+            change: r.change,
+            p_change: r.p_change,
+            previous_close: r.previous_close,
+            open: r.open,
+            day_high: r.day_high,
+            day_low: r.day_low,
+            volume: r.total_traded_volume,
+            value: r.total_traded_value,
+            year_high: r.year_high,
+            year_low: r.year_low,
+        }
+    }
+}
+
+impl From<&PriceBandRecord> for MoverRow {
+    fn from(r: &PriceBandRecord) -> Self {
+        MoverRow {
+            symbol: r.symbol.clone(),
+            series: r.series.clone(),
+            last_price: r.ltp,
+            change: r.change,
+            p_change: r.p_change,
+            previous_close: r.change.map(|change| r.ltp - change),
+            open: None,
+            day_high: None,
+            day_low: None,
+            volume: r.total_traded_vol,
+            value: r.turnover.map(|t| t * numbers::RUPEES_PER_LAKH),
+            year_high: r.year_high,
+            year_low: r.year_low,
+        }
+    }
+}
@@ -0,0 +1,277 @@
+// src/models/option_chain.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use crate::{dates, numbers};
+use crate::pricing::{self, Greeks, OptionInputs, OptionType, PricingConfig};
+
+/// Option chain as returned by NSE's `option-chain-indices` and
+/// `option-chain-equities` endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionChain {
+    #[serde(rename = "records")]
+    pub records: OptionChainRecords,
+    #[serde(rename = "filtered", default)]
+    pub filtered: Option<FilteredOptionChain>,
+}
+
+/// Every strike for every listed expiry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionChainRecords {
+    #[serde(rename = "expiryDates", with = "dates::nse_date_seq")]
+    pub expiry_dates: Vec<NaiveDate>,
+    #[serde(rename = "data")]
+    pub data: Vec<OptionChainRow>,
+    #[serde(rename = "timestamp", with = "dates::ist_datetime")]
+    pub timestamp: DateTime<FixedOffset>,
+    #[serde(rename = "underlyingValue", with = "numbers::required")]
+    pub underlying_value: f64,
+    #[serde(rename = "strikePrices", default)]
+    pub strike_prices: Vec<f64>,
+}
+
+/// NSE's default view: the nearest expiry only, plus CE/PE totals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilteredOptionChain {
+    #[serde(rename = "data")]
+    pub data: Vec<OptionChainRow>,
+    #[serde(rename = "CE", default)]
+    pub ce_totals: Option<OptionTotals>,
+    #[serde(rename = "PE", default)]
+    pub pe_totals: Option<OptionTotals>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionTotals {
+    #[serde(rename = "totOI", with = "numbers::required")]
+    pub total_oi: f64,
+    #[serde(rename = "totVol", with = "numbers::required")]
+    pub total_volume: f64,
+}
+
+/// One strike of one expiry, with its call and put legs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionChainRow {
+    #[serde(rename = "strikePrice", with = "numbers::required")]
+    pub strike_price: f64,
+    #[serde(rename = "expiryDate", with = "dates::nse_date")]
+    pub expiry_date: NaiveDate,
+    #[serde(rename = "CE", default)]
+    pub ce: Option<OptionLeg>,
+    #[serde(rename = "PE", default)]
+    pub pe: Option<OptionLeg>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionLeg {
+    #[serde(rename = "strikePrice", with = "numbers::required")]
+    pub strike_price: f64,
+    #[serde(rename = "expiryDate", with = "dates::nse_date")]
+    pub expiry_date: NaiveDate,
+    #[serde(rename = "underlying")]
+    pub underlying: String,
+    #[serde(rename = "identifier")]
+    pub identifier: String,
+    #[serde(rename = "openInterest", with = "numbers::required")]
+    pub open_interest: f64,
+    #[serde(rename = "changeinOpenInterest", with = "numbers::required")]
+    pub change_in_open_interest: f64,
+    #[serde(rename = "pchangeinOpenInterest", default, with = "numbers::option")]
+    pub p_change_in_open_interest: Option<f64>,
+    #[serde(rename = "totalTradedVolume", with = "numbers::required")]
+    pub total_traded_volume: u64,
+    #[serde(rename = "impliedVolatility", default, with = "numbers::option")]
+    pub implied_volatility: Option<f64>,
+    #[serde(rename = "lastPrice", with = "numbers::required")]
+    pub last_price: f64,
+    #[serde(rename = "change", default, with = "numbers::option")]
+    pub change: Option<f64>,
+    #[serde(rename = "pChange", default, with = "numbers::option")]
+    pub p_change: Option<f64>,
+    #[serde(rename = "totalBuyQuantity", default, with = "numbers::option")]
+    pub total_buy_quantity: Option<u64>,
+    #[serde(rename = "totalSellQuantity", default, with = "numbers::option")]
+    pub total_sell_quantity: Option<u64>,
+    #[serde(rename = "bidQty", default, with = "numbers::option")]
+    pub bid_qty: Option<u64>,
+    #[serde(rename = "bidprice", default, with = "numbers::option")]
+    pub bid_price: Option<f64>,
+    #[serde(rename = "askQty", default, with = "numbers::option")]
+    pub ask_qty: Option<u64>,
+    #[serde(rename = "askPrice", default, with = "numbers::option")]
+    pub ask_price: Option<f64>,
+    #[serde(rename = "underlyingValue", with = "numbers::required")]
+    pub underlying_value: f64,
+
+    // Fields filled in by OptionChain::calculate_greeks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub computed_iv: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub greeks: Option<Greeks>,
+}
+
+impl OptionLeg {
+    /// Premium used for IV: the bid/ask mid when both sides are quoted, else the last price
+    pub fn reference_price(&self) -> f64 {
+        match (self.bid_price, self.ask_price) {
+            (Some(bid), Some(ask)) if bid > 0.0 && ask > 0.0 => 0.5 * (bid + ask),
+            _ => self.last_price,
+        }
+    }
+
+    fn calculate_greeks(&mut self, option_type: OptionType, spot: f64, config: &PricingConfig, now: DateTime<FixedOffset>) {
+        let inputs = OptionInputs {
+            option_type,
+            spot,
+            strike: self.strike_price,
+            time: pricing::time_to_expiry(now, self.expiry_date, config.trading_days_per_year),
+            rate: config.risk_free_rate,
+            dividend_yield: config.dividend_yield,
+            trading_days_per_year: config.trading_days_per_year,
+        };
+
+        // An untraded leg without quotes has nothing to price, and NSE's IV for it is stale
+        let price = self.reference_price();
+        if price <= 0.0 {
+            self.computed_iv = None;
+            self.greeks = None;
+            return;
+        }
+
+        self.computed_iv = inputs.implied_volatility(price);
+
+        // Fall back to NSE's own IV (quoted in percent) when the solver has no answer
+        let volatility = self.computed_iv.or_else(|| {
+            self.implied_volatility
+                .filter(|iv| *iv > 0.0)
+                .map(|iv| iv / 100.0)
+        });
+        self.greeks = volatility.and_then(|vol| inputs.greeks(vol));
+    }
+}
+
+/// Criteria for narrowing an option chain.
+///
+/// Unset fields do not filter; strike bounds are inclusive.
+#[derive(Debug, Clone, Default)]
+pub struct OptionChainFilter {
+    pub expiry: Option<NaiveDate>,
+    pub min_strike: Option<f64>,
+    pub max_strike: Option<f64>,
+}
+
+impl OptionChainFilter {
+    pub fn expiry(mut self, expiry: NaiveDate) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    pub fn strike_range(mut self, min_strike: f64, max_strike: f64) -> Self {
+        self.min_strike = Some(min_strike);
+        self.max_strike = Some(max_strike);
+        self
+    }
+
+    pub fn matches(&self, row: &OptionChainRow) -> bool {
+        unset_or(self.expiry, |expiry| row.expiry_date == expiry)
+            && unset_or(self.min_strike, |min| row.strike_price >= min)
+            && unset_or(self.max_strike, |max| row.strike_price <= max)
+    }
+}
+
+/// Whether a filter criterion is unset or holds; `Option::is_none_or` needs Rust 1.82
+fn unset_or<T>(criterion: Option<T>, holds: impl FnOnce(T) -> bool) -> bool {
+    match criterion {
+        Some(value) => holds(value),
+        None => true,
+    }
+}
+
+impl OptionChain {
+    pub fn underlying_value(&self) -> f64 {
+        self.records.underlying_value
+    }
+
+    /// Nearest listed expiry
+    pub fn nearest_expiry(&self) -> Option<NaiveDate> {
+        self.records.expiry_dates.iter().min().copied()
+    }
+
+    /// Rows across all expiries that match `filter`, ordered by expiry then strike
+    pub fn rows(&self, filter: &OptionChainFilter) -> Vec<&OptionChainRow> {
+        let mut rows: Vec<&OptionChainRow> = self.records.data
+            .iter()
+            .filter(|row| filter.matches(row))
+            .collect();
+        rows.sort_by(|a, b| {
+            a.expiry_date
+                .cmp(&b.expiry_date)
+                .then(a.strike_price.total_cmp(&b.strike_price))
+        });
+        rows
+    }
+
+    /// Copy of the chain keeping only the rows that match `filter`
+    pub fn filter(&self, filter: &OptionChainFilter) -> OptionChain {
+        let data: Vec<OptionChainRow> = self.rows(filter).into_iter().cloned().collect();
+
+        let mut expiry_dates: Vec<NaiveDate> = data.iter().map(|row| row.expiry_date).collect();
+        expiry_dates.sort();
+        expiry_dates.dedup();
+
+        let mut strike_prices: Vec<f64> = data.iter().map(|row| row.strike_price).collect();
+        strike_prices.sort_by(f64::total_cmp);
+        strike_prices.dedup();
+
+        OptionChain {
+            records: OptionChainRecords {
+                expiry_dates,
+                data,
+                timestamp: self.records.timestamp,
+                underlying_value: self.records.underlying_value,
+                strike_prices,
+            },
+            filtered: None,
+        }
+    }
+
+    /// Solve IV and compute Greeks for every leg, priced off the chain's underlying value
+    pub fn calculate_greeks(&mut self, config: &PricingConfig, now: DateTime<FixedOffset>) {
+        let spot = self.records.underlying_value;
+
+        let rows = self.records.data
+            .iter_mut()
+            .chain(self.filtered.iter_mut().flat_map(|f| f.data.iter_mut()));
+
+        for row in rows {
+            if let Some(ce) = row.ce.as_mut() {
+                ce.calculate_greeks(OptionType::Call, spot, config, now);
+            }
+            if let Some(pe) = row.pe.as_mut() {
+                pe.calculate_greeks(OptionType::Put, spot, config, now);
+            }
+        }
+    }
+
+    /// Strikes of one expiry within `width` strikes either side of the underlying
+    pub fn around_atm(&self, expiry: NaiveDate, width: usize) -> Vec<&OptionChainRow> {
+        let rows = self.rows(&OptionChainFilter::default().expiry(expiry));
+        let underlying = self.records.underlying_value;
+
+        let Some(atm) = rows
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                (a.strike_price - underlying)
+                    .abs()
+                    .total_cmp(&(b.strike_price - underlying).abs())
+            })
+            .map(|(i, _)| i)
+        else {
+            return Vec::new();
+        };
+
+        let start = atm.saturating_sub(width);
+        let end = (atm + width + 1).min(rows.len());
+        rows[start..end].to_vec()
+    }
+}
@@ -0,0 +1,159 @@
+// src/models/pre_open.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset};
+use crate::{dates, numbers};
+
+/// Stock groups NSE's pre-open page can be filtered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreOpenSegment {
+    Nifty,
+    BankNifty,
+    /// Stocks in the F&O segment
+    FnO,
+    All,
+    Sme,
+    Others,
+}
+
+impl PreOpenSegment {
+    /// Value of the endpoint's `key` parameter
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            PreOpenSegment::Nifty => "NIFTY",
+            PreOpenSegment::BankNifty => "BANKNIFTY",
+            PreOpenSegment::FnO => "FO",
+            PreOpenSegment::All => "ALL",
+            PreOpenSegment::Sme => "SME",
+            PreOpenSegment::Others => "OTHERS",
+        }
+    }
+}
+
+/// Response of NSE's `market-data-pre-open` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreOpenMarket {
+    #[serde(rename = "advances", default, with = "numbers::option")]
+    pub advances: Option<u32>,
+    #[serde(rename = "declines", default, with = "numbers::option")]
+    pub declines: Option<u32>,
+    #[serde(rename = "unchanged", default, with = "numbers::option")]
+    pub unchanged: Option<u32>,
+    #[serde(rename = "data")]
+    pub data: Vec<PreOpenRow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreOpenRow {
+    #[serde(rename = "metadata")]
+    pub metadata: PreOpenMetadata,
+    #[serde(rename = "detail", default)]
+    pub detail: Option<PreOpenDetail>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreOpenMetadata {
+    #[serde(rename = "symbol")]
+    pub symbol: String,
+    #[serde(rename = "identifier", default)]
+    pub identifier: Option<String>,
+    /// Indicative equilibrium price
+    #[serde(rename = "iep", default, with = "numbers::option")]
+    pub iep: Option<f64>,
+    #[serde(rename = "lastPrice", default, with = "numbers::option")]
+    pub last_price: Option<f64>,
+    /// Change of the IEP from the previous close
+    #[serde(rename = "change", default, with = "numbers::option")]
+    pub change: Option<f64>,
+    #[serde(rename = "pChange", default, with = "numbers::option")]
+    pub p_change: Option<f64>,
+    #[serde(rename = "previousClose", default, with = "numbers::option")]
+    pub previous_close: Option<f64>,
+    #[serde(rename = "finalQuantity", default, with = "numbers::option")]
+    pub final_quantity: Option<u64>,
+    #[serde(rename = "totalTurnover", default, with = "numbers::option")]
+    pub total_turnover: Option<f64>,
+    #[serde(rename = "marketCap", default, with = "numbers::option")]
+    pub market_cap: Option<f64>,
+    #[serde(rename = "yearHigh", default, with = "numbers::option")]
+    pub year_high: Option<f64>,
+    #[serde(rename = "yearLow", default, with = "numbers::option")]
+    pub year_low: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreOpenDetail {
+    #[serde(rename = "preOpenMarket")]
+    pub pre_open_market: PreOpenBook,
+}
+
+/// The pre-open order book of one stock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreOpenBook {
+    #[serde(rename = "preopen", default)]
+    pub levels: Vec<PreOpenLevel>,
+    #[serde(rename = "IEP", default, with = "numbers::option")]
+    pub iep: Option<f64>,
+    #[serde(rename = "finalPrice", default, with = "numbers::option")]
+    pub final_price: Option<f64>,
+    #[serde(rename = "finalQuantity", default, with = "numbers::option")]
+    pub final_quantity: Option<u64>,
+    #[serde(rename = "totalBuyQuantity", default, with = "numbers::option")]
+    pub total_buy_quantity: Option<u64>,
+    #[serde(rename = "totalSellQuantity", default, with = "numbers::option")]
+    pub total_sell_quantity: Option<u64>,
+    /// Buy quantity at market (at the open)
+    #[serde(rename = "atoBuyQty", default, with = "numbers::option")]
+    pub ato_buy_qty: Option<u64>,
+    #[serde(rename = "atoSellQty", default, with = "numbers::option")]
+    pub ato_sell_qty: Option<u64>,
+    #[serde(rename = "lastUpdateTime", default, with = "dates::option_ist_datetime")]
+    pub last_update_time: Option<DateTime<FixedOffset>>,
+}
+
+/// One price level of the pre-open book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreOpenLevel {
+    #[serde(rename = "price", with = "numbers::required")]
+    pub price: f64,
+    #[serde(rename = "buyQty", default, with = "numbers::option")]
+    pub buy_qty: Option<u64>,
+    #[serde(rename = "sellQty", default, with = "numbers::option")]
+    pub sell_qty: Option<u64>,
+    /// Set on the level the equilibrium price was discovered at
+    #[serde(rename = "iep", default)]
+    pub is_iep: bool,
+}
+
+impl PreOpenRow {
+    pub fn symbol(&self) -> &str {
+        &self.metadata.symbol
+    }
+
+    /// Price levels of the order book, empty when NSE sent no detail
+    pub fn order_book(&self) -> &[PreOpenLevel] {
+        self.detail
+            .as_ref()
+            .map(|d| d.pre_open_market.levels.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Total buy quantity over total sell quantity in the pre-open book
+    pub fn buy_sell_ratio(&self) -> Option<f64> {
+        let book = &self.detail.as_ref()?.pre_open_market;
+        let buy = book.total_buy_quantity? as f64;
+        let sell = book.total_sell_quantity? as f64;
+        (sell > 0.0).then(|| buy / sell)
+    }
+}
+
+impl PreOpenMarket {
+    /// Rows ordered by the absolute gap from the previous close, largest first
+    pub fn by_gap(&self) -> Vec<&PreOpenRow> {
+        let mut rows: Vec<&PreOpenRow> = self.data.iter().collect();
+        rows.sort_by(|a, b| {
+            let gap = |row: &PreOpenRow| row.metadata.p_change.map_or(0.0, f64::abs);
+            gap(b).total_cmp(&gap(a))
+        });
+        rows
+    }
+}
@@ -0,0 +1,106 @@
+// src/models/vix.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use crate::{dates, numbers};
+use super::{DailyData, HistoricalData, IndicesData};
+
+/// Name NSE lists the volatility index under
+pub const INDIA_VIX: &str = "INDIA VIX";
+
+/// Current India VIX level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndiaVix {
+    pub last: f64,
+    pub change: f64,
+    pub percent_change: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub previous_close: f64,
+    pub year_high: f64,
+    pub year_low: f64,
+    #[serde(default, with = "dates::option_ist_datetime")]
+    pub timestamp: Option<DateTime<FixedOffset>>,
+}
+
+impl IndiaVix {
+    /// Pick India VIX out of the all-indices snapshot
+    pub fn from_indices(indices: &IndicesData) -> Option<Self> {
+        let vix = indices.data.iter().find(|index| {
+            index.index.eq_ignore_ascii_case(INDIA_VIX) || index.index_symbol.eq_ignore_ascii_case(INDIA_VIX)
+        })?;
+
+        Some(IndiaVix {
+            last: vix.last,
+            change: vix.variation,
+            percent_change: vix.percent_change,
+            open: vix.open,
+            high: vix.high,
+            low: vix.low,
+            previous_close: vix.previous_close,
+            year_high: vix.year_high,
+            year_low: vix.year_low,
+            timestamp: dates::parse_nse_datetime(&indices.timestamp),
+        })
+    }
+}
+
+/// Response of NSE's `historical/vixhistory` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VixHistory {
+    #[serde(rename = "data", default)]
+    pub data: Vec<VixRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VixRecord {
+    #[serde(rename = "EOD_TIMESTAMP", with = "dates::nse_date")]
+    pub date: NaiveDate,
+    #[serde(rename = "EOD_OPEN_INDEX_VAL", with = "numbers::required")]
+    pub open: f64,
+    #[serde(rename = "EOD_HIGH_INDEX_VAL", with = "numbers::required")]
+    pub high: f64,
+    #[serde(rename = "EOD_LOW_INDEX_VAL", with = "numbers::required")]
+    pub low: f64,
+    #[serde(rename = "EOD_CLOSE_INDEX_VAL", with = "numbers::required")]
+    pub close: f64,
+    #[serde(rename = "EOD_PREV_CLOSE", default, with = "numbers::option")]
+    pub prev_close: Option<f64>,
+    #[serde(rename = "VIX_PTS_CHG", default, with = "numbers::option")]
+    pub change: Option<f64>,
+    #[serde(rename = "VIX_PERC_CHG", default, with = "numbers::option")]
+    pub percent_change: Option<f64>,
+}
+
+impl VixHistory {
+    /// Daily rows in the shape of an equity history, sorted by date. Volume and value are zero.
+    pub fn into_historical(self) -> HistoricalData {
+        let mut records = self.data;
+        records.sort_by_key(|r| r.date);
+        records.dedup_by_key(|r| r.date);
+
+        let mut data: Vec<DailyData> = Vec::with_capacity(records.len());
+        for record in records {
+            let prev_close = record.prev_close
+                .or_else(|| data.last().map(|d| d.close))
+                .unwrap_or(record.open);
+            data.push(DailyData {
+                timestamp: record.date,
+                open: record.open,
+                high: record.high,
+                low: record.low,
+                close: record.close,
+                last: record.close,
+                prev_close,
+                ..Default::default()
+            });
+        }
+
+        let mut history = HistoricalData {
+            symbol: INDIA_VIX.to_string(),
+            data,
+        };
+        history.fill_year_range();
+        history
+    }
+}
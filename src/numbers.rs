@@ -0,0 +1,215 @@
+// src/numbers.rs
+//! Lenient deserializers for NSE's numeric fields.
+//!
+//! Illiquid and newly listed symbols come back with numbers quoted
+//! (`"1,234.50"`) or replaced by placeholders such as `"-"`, `""` or `"NA"`.
+//! [`option`] maps placeholders to `None`; [`required`] accepts quoted numbers
+//! but still rejects a missing value.
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserializer, Visitor};
+
+/// Strings NSE uses in place of a number
+const PLACEHOLDERS: [&str; 6] = ["-", "--", "NA", "N/A", "NIL", "NULL"];
+
+/// Rupees in one lakh, the unit of several NSE turnover fields
+pub const RUPEES_PER_LAKH: f64 = 100_000.0;
+
+/// Numeric types the lenient deserializers can produce
+pub trait LenientNumber: Sized {
+    fn from_u64(v: u64) -> Option<Self>;
+    fn from_i64(v: i64) -> Option<Self>;
+    fn from_f64(v: f64) -> Option<Self>;
+}
+
+impl LenientNumber for f64 {
+    fn from_u64(v: u64) -> Option<Self> {
+        Some(v as f64)
+    }
+
+    fn from_i64(v: i64) -> Option<Self> {
+        Some(v as f64)
+    }
+
+    fn from_f64(v: f64) -> Option<Self> {
+        Some(v)
+    }
+}
+
+macro_rules! lenient_integer {
+    ($($t:ty),*) => {
+        $(
+            impl LenientNumber for $t {
+                fn from_u64(v: u64) -> Option<Self> {
+                    <$t>::try_from(v).ok()
+                }
+
+                fn from_i64(v: i64) -> Option<Self> {
+                    <$t>::try_from(v).ok()
+                }
+
+                fn from_f64(v: f64) -> Option<Self> {
+                    // NSE sometimes sends integral quantities as "1200.00"
+                    if v.fract() == 0.0 && v >= <$t>::MIN as f64 && v <= <$t>::MAX as f64 {
+                        Some(v as $t)
+                    } else {
+                        None
+                    }
+                }
+            }
+        )*
+    };
+}
+
+lenient_integer!(u32, u64, i64);
+
+fn parse_str<T: LenientNumber>(s: &str) -> Option<T> {
+    let s = s.trim();
+    if s.is_empty() || PLACEHOLDERS.iter().any(|p| s.eq_ignore_ascii_case(p)) {
+        return None;
+    }
+
+    let cleaned: String = s.chars().filter(|c| *c != ',').collect();
+    if let Ok(v) = cleaned.parse::<u64>() {
+        return T::from_u64(v);
+    }
+    if let Ok(v) = cleaned.parse::<i64>() {
+        return T::from_i64(v);
+    }
+    cleaned.parse::<f64>().ok().filter(|v| v.is_finite()).and_then(T::from_f64)
+}
+
+struct LenientVisitor<T>(PhantomData<T>);
+
+impl<'de, T: LenientNumber> Visitor<'de> for LenientVisitor<T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a number, a numeric string or a placeholder such as \"-\"")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(T::from_u64(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(T::from_i64(v))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(T::from_f64(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(parse_str(v))
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+/// Optional numbers; placeholders, blanks and `null` become `None`.
+///
+/// Pair with `#[serde(default)]` so that a missing field is also `None`.
+pub mod option {
+    use serde::{Deserializer, Serialize, Serializer};
+
+    use super::{LenientNumber, LenientVisitor};
+
+    pub fn serialize<S: Serializer, T: Serialize>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: LenientNumber>(deserializer: D) -> Result<Option<T>, D::Error> {
+        deserializer.deserialize_option(LenientVisitor(std::marker::PhantomData))
+    }
+}
+
+/// Numbers that may arrive quoted but must be present
+pub mod required {
+    use serde::{de, Deserializer, Serialize, Serializer};
+
+    use super::{LenientNumber, LenientVisitor};
+
+    pub fn serialize<S: Serializer, T: Serialize>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: LenientNumber>(deserializer: D) -> Result<T, D::Error> {
+        deserializer
+            .deserialize_option(LenientVisitor(std::marker::PhantomData))?
+            .ok_or_else(|| de::Error::custom("expected a number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::LenientNumber;
+
+    #[derive(Deserialize)]
+    struct Row {
+        #[serde(default, with = "super::option")]
+        value: Option<f64>,
+    }
+
+    fn option<T: LenientNumber>(raw: &str) -> Option<T> {
+        super::option::deserialize(&mut serde_json::Deserializer::from_str(raw)).unwrap()
+    }
+
+    fn required<T: LenientNumber>(raw: &str) -> Result<T, serde_json::Error> {
+        super::required::deserialize(&mut serde_json::Deserializer::from_str(raw))
+    }
+
+    #[test]
+    fn placeholders_are_none() {
+        for raw in [r#""-""#, r#""""#, r#""NA""#, r#""n/a""#, r#"" -- ""#, "null", "true"] {
+            assert_eq!(option::<f64>(raw), None, "{}", raw);
+        }
+        let missing: Row = serde_json::from_str("{}").unwrap();
+        assert_eq!(missing.value, None);
+    }
+
+    #[test]
+    fn reads_plain_and_quoted_numbers() {
+        assert_eq!(option::<f64>("1234.5"), Some(1234.5));
+        assert_eq!(option::<f64>(r#""1234.5""#), Some(1234.5));
+        assert_eq!(option::<f64>(r#""1,234.50""#), Some(1234.5));
+        assert_eq!(option::<f64>(r#""-12.75""#), Some(-12.75));
+        assert_eq!(option::<i64>(r#""-1,200""#), Some(-1200));
+    }
+
+    #[test]
+    fn integral_decimals_fit_integers() {
+        assert_eq!(option::<u64>(r#""1200.00""#), Some(1200));
+        assert_eq!(option::<u64>("1200.0"), Some(1200));
+        assert_eq!(option::<u64>(r#""1,200.00""#), Some(1200));
+        assert_eq!(option::<u64>(r#""1200.50""#), None);
+        assert_eq!(option::<u64>("-5"), None);
+        assert_eq!(option::<u32>("5000000000"), None);
+    }
+
+    #[test]
+    fn required_rejects_blanks() {
+        assert_eq!(required::<f64>(r#""1,234.50""#).unwrap(), 1234.5);
+        assert_eq!(required::<u64>(r#""1200.00""#).unwrap(), 1200);
+        assert!(required::<f64>(r#""""#).is_err());
+        assert!(required::<f64>(r#""-""#).is_err());
+        assert!(required::<f64>("null").is_err());
+    }
+}
@@ -0,0 +1,335 @@
+// src/pricing.rs
+//! Black-Scholes pricing, Greeks and implied volatility for NSE options.
+//!
+//! NSE index and stock options are European-style, so the plain
+//! Black-Scholes-Merton model applies. Time is measured in trading years:
+//! IST trading days to expiry divided by `trading_days_per_year`. Volatility,
+//! the risk-free rate and the dividend yield are all taken per trading year,
+//! and theta is reported per trading day.
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::dates;
+
+/// Lowest volatility the IV solver will consider
+const MIN_VOLATILITY: f64 = 1e-4;
+/// Highest volatility the IV solver will consider (500%)
+const MAX_VOLATILITY: f64 = 5.0;
+/// Convergence tolerance of the IV solver, relative to the option price
+const IV_TOLERANCE: f64 = 1e-10;
+/// Volatility bracket narrow enough to stop bisecting
+const IV_MIN_BRACKET: f64 = 1e-12;
+const IV_MAX_ITERATIONS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+/// Inputs that are not part of the option chain
+#[derive(Debug, Clone)]
+pub struct PricingConfig {
+    /// Continuously compounded risk-free rate per trading year, e.g. 0.07 for 7%.
+    ///
+    /// A trading year spans roughly one calendar year, so a quoted annual rate can be used as is.
+    pub risk_free_rate: f64,
+    /// Continuous dividend yield of the underlying per trading year
+    pub dividend_yield: f64,
+    /// Trading days that make up one trading year
+    pub trading_days_per_year: f64,
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self {
+            risk_free_rate: 0.07,
+            dividend_yield: 0.0,
+            trading_days_per_year: 252.0,
+        }
+    }
+}
+
+/// Sensitivities of an option's price.
+///
+/// Theta is per trading day, vega per one volatility point (1%) and rho
+/// per one percentage point of the risk-free rate.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Greeks {
+    pub delta: f64,
+    pub gamma: f64,
+    pub theta: f64,
+    pub vega: f64,
+    pub rho: f64,
+}
+
+/// Market inputs for a single option contract
+#[derive(Debug, Clone, Copy)]
+pub struct OptionInputs {
+    pub option_type: OptionType,
+    pub spot: f64,
+    pub strike: f64,
+    /// Time to expiry in trading years, see [`time_to_expiry`]
+    pub time: f64,
+    /// Risk-free rate per trading year
+    pub rate: f64,
+    /// Dividend yield per trading year
+    pub dividend_yield: f64,
+    /// Trading days in a year, used to express theta per trading day
+    pub trading_days_per_year: f64,
+}
+
+impl OptionInputs {
+    fn d1_d2(&self, volatility: f64) -> (f64, f64) {
+        let sqrt_t = self.time.sqrt();
+        let d1 = ((self.spot / self.strike).ln()
+            + (self.rate - self.dividend_yield + 0.5 * volatility * volatility) * self.time)
+            / (volatility * sqrt_t);
+        (d1, d1 - volatility * sqrt_t)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.spot > 0.0 && self.strike > 0.0 && self.time > 0.0
+    }
+
+    /// Value at expiry, used when no time is left
+    fn intrinsic(&self) -> f64 {
+        match self.option_type {
+            OptionType::Call => (self.spot - self.strike).max(0.0),
+            OptionType::Put => (self.strike - self.spot).max(0.0),
+        }
+    }
+
+    /// Black-Scholes price for the given volatility
+    pub fn price(&self, volatility: f64) -> f64 {
+        if !self.is_valid() || volatility <= 0.0 {
+            return self.intrinsic();
+        }
+
+        let (d1, d2) = self.d1_d2(volatility);
+        let spot_df = self.spot * (-self.dividend_yield * self.time).exp();
+        let strike_df = self.strike * (-self.rate * self.time).exp();
+
+        match self.option_type {
+            OptionType::Call => spot_df * norm_cdf(d1) - strike_df * norm_cdf(d2),
+            OptionType::Put => strike_df * norm_cdf(-d2) - spot_df * norm_cdf(-d1),
+        }
+    }
+
+    pub fn greeks(&self, volatility: f64) -> Option<Greeks> {
+        if !self.is_valid() || volatility <= 0.0 {
+            return None;
+        }
+
+        let (d1, d2) = self.d1_d2(volatility);
+        let sqrt_t = self.time.sqrt();
+        let q_df = (-self.dividend_yield * self.time).exp();
+        let r_df = (-self.rate * self.time).exp();
+        let pdf_d1 = norm_pdf(d1);
+
+        let gamma = q_df * pdf_d1 / (self.spot * volatility * sqrt_t);
+        let vega = self.spot * q_df * pdf_d1 * sqrt_t;
+        let decay = -self.spot * q_df * pdf_d1 * volatility / (2.0 * sqrt_t);
+
+        let (delta, theta, rho) = match self.option_type {
+            OptionType::Call => (
+                q_df * norm_cdf(d1),
+                decay - self.rate * self.strike * r_df * norm_cdf(d2)
+                    + self.dividend_yield * self.spot * q_df * norm_cdf(d1),
+                self.strike * self.time * r_df * norm_cdf(d2),
+            ),
+            OptionType::Put => (
+                q_df * (norm_cdf(d1) - 1.0),
+                decay + self.rate * self.strike * r_df * norm_cdf(-d2)
+                    - self.dividend_yield * self.spot * q_df * norm_cdf(-d1),
+                -self.strike * self.time * r_df * norm_cdf(-d2),
+            ),
+        };
+
+        Some(Greeks {
+            delta,
+            gamma,
+            theta: theta / self.trading_days_per_year,
+            vega: vega / 100.0,
+            rho: rho / 100.0,
+        })
+    }
+
+    /// Volatility at which the model price equals `price`.
+    ///
+    /// Starts with Newton-Raphson and falls back to bisection whenever a
+    /// Newton step leaves the bracket or vega vanishes, so it converges for
+    /// deep in- and out-of-the-money strikes too. Returns `None` when the
+    /// price is outside the no-arbitrage bounds.
+    pub fn implied_volatility(&self, price: f64) -> Option<f64> {
+        if !self.is_valid() || !price.is_finite() || price <= 0.0 {
+            return None;
+        }
+
+        let mut low = MIN_VOLATILITY;
+        let mut high = MAX_VOLATILITY;
+        let f_low = self.price(low) - price;
+        let f_high = self.price(high) - price;
+        if f_low > 0.0 || f_high < 0.0 {
+            return None;
+        }
+
+        let tolerance = IV_TOLERANCE * price.max(1.0);
+        let mut vol = 0.3;
+        for _ in 0..IV_MAX_ITERATIONS {
+            let diff = self.price(vol) - price;
+            if diff.abs() < tolerance {
+                return Some(vol);
+            }
+
+            // Price is increasing in volatility, which keeps the bracket valid
+            if diff > 0.0 {
+                high = vol;
+            } else {
+                low = vol;
+            }
+
+            let (d1, _) = self.d1_d2(vol);
+            let vega = self.spot * (-self.dividend_yield * self.time).exp() * norm_pdf(d1) * self.time.sqrt();
+            let newton = vol - diff / vega;
+
+            vol = if vega > 1e-12 && newton > low && newton < high {
+                newton
+            } else {
+                0.5 * (low + high)
+            };
+
+            if high - low < IV_MIN_BRACKET {
+                return Some(vol);
+            }
+        }
+
+        Some(vol)
+    }
+}
+
+/// Trading years from `now` until the 15:30 IST close on `expiry`.
+///
+/// Counts the remaining fraction of today's session plus every weekday
+/// after today up to and including the expiry day. Exchange holidays are
+/// not known here and count as trading days.
+pub fn time_to_expiry(now: DateTime<FixedOffset>, expiry: NaiveDate, trading_days_per_year: f64) -> f64 {
+    let now = now.with_timezone(&dates::ist());
+    let today = now.date_naive();
+    if expiry < today {
+        return 0.0;
+    }
+
+    let open = NaiveTime::from_hms_opt(9, 15, 0).expect("valid time");
+    let close = NaiveTime::from_hms_opt(15, 30, 0).expect("valid time");
+    let session_secs = (close - open).num_seconds() as f64;
+
+    let mut days = 0.0;
+    if is_weekday(today) {
+        let time = now.time().clamp(open, close);
+        days += (close - time).num_seconds() as f64 / session_secs;
+    }
+
+    let mut day = today;
+    while day < expiry {
+        day = day.succ_opt().expect("date in range");
+        if is_weekday(day) {
+            days += 1.0;
+        }
+    }
+
+    days / trading_days_per_year
+}
+
+fn is_weekday(date: NaiveDate) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+fn norm_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function.
+///
+/// W. J. Cody's rational Chebyshev approximations (Math. Comp. 23, 1969),
+/// as used in his CALERF routine; relative error is close to machine precision.
+// The coefficients are kept digit for digit as published
+#[allow(clippy::excessive_precision)]
+fn erfc(x: f64) -> f64 {
+    const A: [f64; 5] = [
+        3.16112374387056560e00, 1.13864154151050156e02, 3.77485237685302021e02,
+        3.20937758913846947e03, 1.85777706184603153e-1,
+    ];
+    const B: [f64; 4] = [
+        2.36012909523441209e01, 2.44024637934444173e02, 1.28261652607737228e03,
+        2.84423683343917062e03,
+    ];
+    const C: [f64; 9] = [
+        5.64188496988670089e-1, 8.88314979438837594e00, 6.61191906371416295e01,
+        2.98635138197400131e02, 8.81952221241769090e02, 1.71204761263407058e03,
+        2.05107837782607147e03, 1.23033935479799725e03, 2.15311535474403846e-8,
+    ];
+    const D: [f64; 8] = [
+        1.57449261107098347e01, 1.17693950891312499e02, 5.37181101862009858e02,
+        1.62138957456669019e03, 3.29079923573345963e03, 4.36261909014324716e03,
+        3.43936767414372164e03, 1.23033935480374942e03,
+    ];
+    const P: [f64; 6] = [
+        3.05326634961232344e-1, 3.60344899949804439e-1, 1.25781726111229246e-1,
+        1.60837851487422766e-2, 6.58749161529837803e-4, 1.63153871373020978e-2,
+    ];
+    const Q: [f64; 5] = [
+        2.56852019228982242e00, 1.87295284992346725e00, 5.27905102951428412e-1,
+        6.05183413124413191e-2, 2.33520497626869185e-3,
+    ];
+    /// Beyond this erfc underflows
+    const X_BIG: f64 = 26.543;
+
+    let y = x.abs();
+    if y <= 0.46875 {
+        // erf(x) = x * A(x^2) / B(x^2)
+        let ysq = y * y;
+        let (mut num, mut den) = (A[4] * ysq, ysq);
+        for (a, b) in A.iter().zip(&B).take(3) {
+            num = (num + a) * ysq;
+            den = (den + b) * ysq;
+        }
+        return 1.0 - x * (num + A[3]) / (den + B[3]);
+    }
+
+    // erfc(y) = exp(-y^2) * R(y)
+    let r = if y <= 4.0 {
+        let (mut num, mut den) = (C[8] * y, y);
+        for (c, d) in C.iter().zip(&D).take(7) {
+            num = (num + c) * y;
+            den = (den + d) * y;
+        }
+        (num + C[7]) / (den + D[7])
+    } else if y < X_BIG {
+        let ysq = 1.0 / (y * y);
+        let (mut num, mut den) = (P[5] * ysq, ysq);
+        for (p, q) in P.iter().zip(&Q).take(4) {
+            num = (num + p) * ysq;
+            den = (den + q) * ysq;
+        }
+        let r = ysq * (num + P[4]) / (den + Q[4]);
+        (std::f64::consts::FRAC_2_SQRT_PI / 2.0 - r) / y
+    } else {
+        0.0
+    };
+
+    // exp(-y^2) in two factors, to avoid losing precision in y^2
+    let y_trunc = (y * 16.0).trunc() / 16.0;
+    let del = (y - y_trunc) * (y + y_trunc);
+    let r = (-y_trunc * y_trunc).exp() * (-del).exp() * r;
+
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
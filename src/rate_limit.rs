@@ -0,0 +1,84 @@
+// src/rate_limit.rs
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Client-side request budget, enforced as a token bucket.
+///
+/// The bucket holds up to `burst` tokens and refills at
+/// `requests_per_second`. Every HTTP request sent by `NseScraper`,
+/// including retries and session priming, takes one token.
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    pub requests_per_second: f64,
+    pub burst: u32,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 3.0,
+            burst: 5,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket shared by every clone of an `NseScraper`
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: Option<RateLimit>,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: Option<RateLimit>) -> Self {
+        let limit = limit.filter(|l| l.requests_per_second > 0.0 && l.requests_per_second.is_finite());
+        let burst = limit.as_ref().map(|l| f64::from(l.burst.max(1))).unwrap_or_default();
+
+        Self {
+            limit,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait for a token and return how long the caller was held back.
+    ///
+    /// Tokens are reserved before sleeping, so callers are served in the
+    /// order they arrived even when many tasks contend for the bucket.
+    pub(crate) async fn acquire(&self) -> Duration {
+        let Some(limit) = &self.limit else {
+            return Duration::ZERO;
+        };
+
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            let burst = f64::from(limit.burst.max(1));
+
+            bucket.tokens = (bucket.tokens + elapsed * limit.requests_per_second).min(burst);
+            bucket.last_refill = now;
+            bucket.tokens -= 1.0;
+
+            if bucket.tokens >= 0.0 {
+                Duration::ZERO
+            } else {
+                Duration::from_secs_f64(-bucket.tokens / limit.requests_per_second)
+            }
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+
+        wait
+    }
+}
@@ -0,0 +1,89 @@
+// src/retry.rs
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// How `NseScraper` retries requests that fail for transient reasons.
+///
+/// Only errors for which [`NseScraperError::is_retryable`] holds are
+/// retried: connection failures, timeouts, 429 and 5xx responses. Every
+/// endpoint is a plain GET, so repeating a request is always safe.
+///
+/// [`NseScraperError::is_retryable`]: crate::NseScraperError::is_retryable
+///
+/// The delay before attempt `n + 1` is `base_delay * 2^(n - 1)`, capped at
+/// `max_delay`, with up to `jitter` of it randomly shaved off so that
+/// concurrent tasks do not retry in lock step. A `Retry-After` header sent
+/// by NSE takes precedence over the computed backoff.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound for any single delay, including one requested through `Retry-After`
+    pub max_delay: Duration,
+    /// Fraction (0.0 to 1.0) of each delay that is randomised
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    /// Policy that sends every request exactly once
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Whether another attempt may follow `attempt` (1-based)
+    pub fn allows_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts.max(1)
+    }
+
+    /// Delay to wait after `attempt` (1-based) failed
+    pub fn delay_for(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(16);
+        let backoff = self.base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return backoff;
+        }
+
+        let shave = rand::thread_rng().gen_range(0.0..=jitter);
+        backoff.mul_f64(1.0 - shave)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            jitter: 0.5,
+        }
+    }
+}
+
+/// Parse a `Retry-After` header given either in seconds or as an HTTP date
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = at.with_timezone(&Utc).signed_duration_since(Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
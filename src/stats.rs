@@ -0,0 +1,49 @@
+// src/stats.rs
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Snapshot of the request counters kept by an `NseScraper`.
+///
+/// The counters are cumulative over every call made through the scraper and
+/// its clones; diff two snapshots to measure a stretch of work.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestStats {
+    /// API calls made through the scraper
+    pub calls: u64,
+    /// Attempts made for those calls, including retries and the replay after a session refresh
+    pub attempts: u64,
+    /// Time spent waiting on the rate limiter across all calls
+    pub throttled: Duration,
+}
+
+impl RequestStats {
+    /// Requests that were repeats of an earlier failed attempt
+    pub fn retries(&self) -> u64 {
+        self.attempts.saturating_sub(self.calls)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StatsRecorder {
+    calls: AtomicU64,
+    attempts: AtomicU64,
+    throttled_nanos: AtomicU64,
+}
+
+impl StatsRecorder {
+    pub(crate) fn record_call(&self, attempts: u32, throttled: Duration) {
+        let throttled_nanos = u64::try_from(throttled.as_nanos()).unwrap_or(u64::MAX);
+
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.attempts.fetch_add(u64::from(attempts), Ordering::Relaxed);
+        self.throttled_nanos.fetch_add(throttled_nanos, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> RequestStats {
+        RequestStats {
+            calls: self.calls.load(Ordering::Relaxed),
+            attempts: self.attempts.load(Ordering::Relaxed),
+            throttled: Duration::from_nanos(self.throttled_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
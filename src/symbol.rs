@@ -0,0 +1,123 @@
+// src/symbol.rs
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{NseScraperError, Result};
+
+/// Longest symbol NSE lists, with some headroom
+const MAX_SYMBOL_LEN: usize = 20;
+
+/// A validated NSE trading symbol such as `RELIANCE`, `M&M` or `BAJAJ-AUTO`.
+///
+/// Input is trimmed and upper-cased. NSE symbols consist of ASCII letters,
+/// digits and the characters `&`, `-` and `_`; anything else is rejected
+/// with [`NseScraperError::InvalidSymbol`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(String);
+
+impl Symbol {
+    pub fn new(symbol: &str) -> Result<Self> {
+        let normalized = symbol.trim().to_ascii_uppercase();
+
+        let valid_chars = normalized
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '&' | '-' | '_'));
+        let starts_alphanumeric = normalized
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric());
+
+        if !valid_chars || !starts_alphanumeric || normalized.len() > MAX_SYMBOL_LEN {
+            return Err(NseScraperError::InvalidSymbol(symbol.to_string()));
+        }
+
+        Ok(Self(normalized))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = NseScraperError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<&str> for Symbol {
+    type Error = NseScraperError;
+
+    fn try_from(s: &str) -> Result<Self> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Symbol {
+    type Error = NseScraperError;
+
+    fn try_from(s: String) -> Result<Self> {
+        Self::new(&s)
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Symbol::new(&raw).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_symbols_with_punctuation() {
+        for symbol in ["M&M", "BAJAJ-AUTO", "L&TFH", "J&KBANK", "NIFTY_50"] {
+            assert_eq!(Symbol::new(symbol).unwrap().as_str(), symbol);
+        }
+    }
+
+    #[test]
+    fn trims_and_upper_cases() {
+        assert_eq!(Symbol::new("  m&m ").unwrap().as_str(), "M&M");
+        assert_eq!("bajaj-auto".parse::<Symbol>().unwrap().to_string(), "BAJAJ-AUTO");
+    }
+
+    #[test]
+    fn rejects_invalid_symbols() {
+        for symbol in ["", "   ", "-ABC", "&M", "RELI ANCE", "TCS?x=1", "A".repeat(21).as_str()] {
+            assert!(matches!(Symbol::new(symbol), Err(NseScraperError::InvalidSymbol(_))), "{:?}", symbol);
+        }
+    }
+
+    #[test]
+    fn deserialization_validates() {
+        let symbol: Symbol = serde_json::from_str(r#""l&tfh""#).unwrap();
+        assert_eq!(symbol.as_str(), "L&TFH");
+        assert!(serde_json::from_str::<Symbol>(r#""bad symbol""#).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::{date, fixture};
+use nse_scraper::analytics::{self, OiBuildup};
+use nse_scraper::models::OptionChain;
+
+fn nifty_chain() -> OptionChain {
+    fixture("option_chain_nifty.json")
+}
+
+#[test]
+fn put_call_ratio_by_oi_and_volume() {
+    let chain = nifty_chain();
+    let near = analytics::analyze_expiry(&chain, date(2024, 3, 28)).unwrap();
+
+    assert_eq!(near.total_call_oi, 700.0);
+    assert_eq!(near.total_put_oi, 600.0);
+    assert!((near.pcr_oi.unwrap() - 600.0 / 700.0).abs() < 1e-12);
+    assert_eq!(near.pcr_volume, Some(1.0));
+}
+
+#[test]
+fn max_pain_strike() {
+    let chain = nifty_chain();
+    let near = analytics::analyze_expiry(&chain, date(2024, 3, 28)).unwrap();
+
+    assert_eq!(near.max_pain, Some(22000.0));
+}
+
+#[test]
+fn oi_buildup_per_strike() {
+    let chain = nifty_chain();
+    let near = analytics::analyze_expiry(&chain, date(2024, 3, 28)).unwrap();
+
+    let calls: Vec<_> = near.buildup.iter().map(|b| b.call).collect();
+    let puts: Vec<_> = near.buildup.iter().map(|b| b.put).collect();
+
+    assert_eq!(
+        calls,
+        vec![
+            Some(OiBuildup::LongBuildup),
+            Some(OiBuildup::LongUnwinding),
+            Some(OiBuildup::Neutral),
+        ]
+    );
+    assert_eq!(
+        puts,
+        vec![
+            Some(OiBuildup::ShortBuildup),
+            Some(OiBuildup::ShortCovering),
+            Some(OiBuildup::LongBuildup),
+        ]
+    );
+}
+
+#[test]
+fn analyzes_every_expiry_in_order() {
+    let chain = nifty_chain();
+    let all = analytics::analyze(&chain);
+
+    let expiries: Vec<_> = all.iter().map(|a| a.expiry).collect();
+    assert_eq!(expiries, vec![date(2024, 3, 28), date(2024, 4, 4)]);
+
+    // The far expiry only has a call leg
+    let far = &all[1];
+    assert_eq!(far.pcr_oi, Some(0.0));
+    assert_eq!(far.buildup[0].put, None);
+    assert_eq!(far.buildup[0].call, Some(OiBuildup::Neutral));
+}
@@ -0,0 +1,250 @@
+mod common;
+
+use std::io::{Cursor, Write};
+use std::time::Duration;
+
+use common::{date, LoopbackServer, Reply};
+use nse_scraper::bhavcopy::{self, BhavcopyLayout};
+use nse_scraper::models::ContractKind;
+use nse_scraper::pricing::OptionType;
+use nse_scraper::{Endpoint, NseScraper, NseScraperError};
+
+const LEGACY: &str = include_str!("fixtures/cm_bhavcopy_legacy.csv");
+const UDIFF: &str = include_str!("fixtures/cm_bhavcopy_udiff.csv");
+const FO_LEGACY: &str = include_str!("fixtures/fo_bhavcopy_legacy.csv");
+const FO_UDIFF: &str = include_str!("fixtures/fo_bhavcopy_udiff.csv");
+
+fn zipped(name: &str, contents: &str) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    writer.start_file(name, zip::write::FileOptions::default()).unwrap();
+    writer.write_all(contents.as_bytes()).unwrap();
+    writer.finish().unwrap().into_inner()
+}
+
+#[test]
+fn parses_legacy_layout() {
+    let rows = bhavcopy::parse_cm_csv(LEGACY.as_bytes()).unwrap();
+    assert_eq!(rows.len(), 4);
+
+    let infy = rows.iter().find(|r| r.symbol == "INFY").unwrap();
+    assert_eq!(infy.series, "EQ");
+    assert_eq!(infy.date, date(2024, 7, 1));
+    assert_eq!(infy.close, 1660.15);
+    assert_eq!(infy.volume, 4817342);
+    assert_eq!(infy.trades, Some(152331));
+    assert_eq!(infy.isin.as_deref(), Some("INE009A01021"));
+}
+
+#[test]
+fn parses_udiff_layout() {
+    let rows = bhavcopy::parse_cm_csv(UDIFF.as_bytes()).unwrap();
+    assert_eq!(rows.len(), 3);
+
+    let reliance = rows.iter().find(|r| r.symbol == "RELIANCE" && r.series == "EQ").unwrap();
+    assert_eq!(reliance.date, date(2024, 7, 8));
+    assert_eq!(reliance.open, 3140.0);
+    assert_eq!(reliance.last, 3171.0);
+    assert_eq!(reliance.prev_close, 3138.7);
+    assert_eq!(reliance.volume, 5120987);
+}
+
+#[test]
+fn parses_zipped_bhavcopy() {
+    let archive = zipped("BhavCopy_NSE_CM_0_0_0_20240708_F_0000.csv", UDIFF);
+    let rows = bhavcopy::parse_cm_zip(Cursor::new(archive)).unwrap();
+    assert_eq!(rows.len(), 3);
+}
+
+#[test]
+fn rejects_unknown_csv() {
+    assert!(bhavcopy::parse_cm_csv("a,b\n1,2\n".as_bytes()).is_err());
+    assert!(bhavcopy::parse_cm_csv(FO_LEGACY.as_bytes()).is_err());
+    assert!(bhavcopy::parse_fo_csv(LEGACY.as_bytes()).is_err());
+}
+
+#[test]
+fn parses_legacy_fo_layout() {
+    let rows = bhavcopy::parse_fo_csv(FO_LEGACY.as_bytes()).unwrap();
+    // The VIX future is not a stock or index contract
+    assert_eq!(rows.len(), 5);
+
+    let future = &rows[0];
+    assert_eq!(future.kind, ContractKind::IndexFuture);
+    assert_eq!(future.expiry_date, date(2024, 7, 25));
+    assert_eq!(future.strike_price, None);
+    assert_eq!(future.option_type, None);
+    assert_eq!(future.contracts, 212345);
+    assert_eq!(future.value, 1289456.32 * 100_000.0);
+    assert_eq!(future.change_in_open_interest, -125400.0);
+
+    let put = rows.iter().find(|r| r.symbol == "RELIANCE").unwrap();
+    assert_eq!(put.kind, ContractKind::StockOption);
+    assert_eq!(put.option_type, Some(OptionType::Put));
+    assert_eq!(put.strike_price, Some(3200.0));
+    assert_eq!(put.close, Some(88.35));
+
+    // Untraded contracts are written with zero prices
+    let untraded = rows.iter().find(|r| !r.is_traded()).unwrap();
+    assert_eq!(untraded.strike_price, Some(3400.0));
+    assert_eq!((untraded.open, untraded.close), (None, None));
+    assert_eq!(untraded.settlement_price, Some(2.15));
+    assert_eq!(untraded.open_interest, 45250.0);
+    assert_eq!(untraded.basis(), None);
+}
+
+#[test]
+fn parses_udiff_fo_layout() {
+    let rows = bhavcopy::parse_fo_csv(FO_UDIFF.as_bytes()).unwrap();
+    assert_eq!(rows.len(), 6);
+
+    let call = rows.iter().find(|r| r.option_type == Some(OptionType::Call)).unwrap();
+    assert_eq!(call.kind, ContractKind::IndexOption);
+    assert_eq!(call.strike_price, Some(24500.0));
+    assert_eq!(call.settlement_price, Some(301.2));
+    assert_eq!(call.open_interest, 2456325.0);
+    // Volume is quoted in units; 25 units per lot
+    assert_eq!(call.contracts, 9876550 / 25);
+    assert_eq!(call.underlying_price, Some(24406.1));
+
+    let untraded = rows.iter().find(|r| r.symbol == "RELIANCE").unwrap();
+    assert!(!untraded.is_traded());
+    assert_eq!((untraded.open, untraded.high, untraded.low, untraded.close), (None, None, None, None));
+    assert_eq!(untraded.settlement_price, Some(44.1));
+    assert_eq!(untraded.open_interest, 125000.0);
+    assert_eq!(untraded.basis(), None);
+}
+
+#[test]
+fn udiff_futures_basis_against_underlying() {
+    let rows = bhavcopy::parse_fo_csv(FO_UDIFF.as_bytes()).unwrap();
+    let august = rows
+        .iter()
+        .find(|r| r.is_future() && r.date == date(2024, 7, 25) && r.expiry_date == date(2024, 8, 29))
+        .unwrap();
+    assert!((august.basis().unwrap() - 106.6).abs() < 1e-9);
+
+    // The legacy layout has no underlying price
+    let legacy = bhavcopy::parse_fo_csv(FO_LEGACY.as_bytes()).unwrap();
+    assert_eq!(legacy[0].close, Some(24295.6));
+    assert_eq!(legacy[0].basis(), None);
+}
+
+#[test]
+fn builds_front_month_futures() {
+    let mut rows = bhavcopy::parse_fo_csv(FO_LEGACY.as_bytes()).unwrap();
+    rows.extend(bhavcopy::parse_fo_csv(FO_UDIFF.as_bytes()).unwrap());
+
+    let series = bhavcopy::front_month_futures(&rows, 0);
+    let nifty = &series["NIFTY"];
+    let expiries: Vec<_> = nifty.iter().map(|r| (r.date, r.expiry_date)).collect();
+    assert_eq!(
+        expiries,
+        [
+            (date(2024, 7, 4), date(2024, 7, 25)),
+            (date(2024, 7, 25), date(2024, 7, 25)),
+            (date(2024, 7, 26), date(2024, 8, 29)),
+        ]
+    );
+
+    // Rolling a day early moves expiry day onto the next contract
+    let early = bhavcopy::front_month_futures(&rows, 1);
+    assert_eq!(early["NIFTY"][1].expiry_date, date(2024, 8, 29));
+    assert!(!early.contains_key("RELIANCE"));
+}
+
+#[test]
+fn groups_rows_into_histories() {
+    let mut rows = bhavcopy::parse_cm_csv(LEGACY.as_bytes()).unwrap();
+    rows.extend(bhavcopy::parse_cm_csv(UDIFF.as_bytes()).unwrap());
+
+    let histories = bhavcopy::to_historical(&rows, "EQ");
+    assert_eq!(histories.keys().collect::<Vec<_>>(), ["20MICRONS", "INFY", "RELIANCE"]);
+
+    let reliance = &histories["RELIANCE"].data;
+    assert_eq!(reliance.len(), 2);
+    assert_eq!(reliance[0].timestamp, date(2024, 7, 1));
+    assert_eq!(reliance[1].close, 3172.55);
+    assert_eq!(reliance[1].year_high, 3178.4);
+    assert_eq!(reliance[1].year_low, 3101.1);
+}
+
+#[test]
+fn archive_file_follows_layout_switch() {
+    assert_eq!(BhavcopyLayout::for_date(date(2024, 7, 5)), BhavcopyLayout::Legacy);
+    assert_eq!(
+        bhavcopy::cm_archive_file(date(2024, 7, 5)),
+        (Endpoint::CmBhavcopy, "2024/JUL/cm05JUL2024bhav.csv.zip".to_string())
+    );
+    assert_eq!(
+        bhavcopy::cm_archive_file(date(2024, 7, 8)),
+        (Endpoint::CmBhavcopyUdiff, "BhavCopy_NSE_CM_0_0_0_20240708_F_0000.csv.zip".to_string())
+    );
+    assert_eq!(
+        bhavcopy::fo_archive_file(date(2024, 7, 4)),
+        (Endpoint::FoBhavcopy, "2024/JUL/fo04JUL2024bhav.csv.zip".to_string())
+    );
+    assert_eq!(
+        bhavcopy::fo_archive_file(date(2024, 7, 25)),
+        (Endpoint::FoBhavcopyUdiff, "BhavCopy_NSE_FO_0_0_0_20240725_F_0000.csv.zip".to_string())
+    );
+}
+
+/// Archive server with a UDiFF bhavcopy for every weekday except `holidays`
+async fn archive_stub(holidays: &'static [&'static str], delay: Duration) -> (LoopbackServer, NseScraper) {
+    let server = LoopbackServer::start(move |request, _| {
+        let path = request.path.as_str();
+        if path == "/" {
+            Reply::ok("<html></html>")
+        } else if path.starts_with("/content/cm/") && !holidays.iter().any(|day| path.contains(day)) {
+            Reply::bytes(zipped("bhav.csv", UDIFF)).delay(delay)
+        } else {
+            Reply::status(404)
+        }
+    })
+    .await;
+    let scraper = NseScraper::builder()
+        .base_url(&server.url)
+        .archive_url(&server.url)
+        .without_rate_limit()
+        .build()
+        .unwrap();
+    (server, scraper)
+}
+
+#[tokio::test]
+async fn range_skips_holidays_and_weekends() {
+    let (server, scraper) = archive_stub(&["20240717"], Duration::ZERO).await;
+    let one_day = bhavcopy::parse_cm_csv(UDIFF.as_bytes()).unwrap().len();
+
+    // Monday 15 to Sunday 21 July 2024, with Muharram on Wednesday
+    let rows = scraper.get_cm_bhavcopy_range(date(2024, 7, 15), date(2024, 7, 21)).await.unwrap();
+
+    assert_eq!(rows.len(), 4 * one_day);
+    assert_eq!(server.requests().len(), 1 + 5);
+    assert!(server.requests_to("/content/cm/BhavCopy_NSE_CM_0_0_0_20240720_F_0000.csv.zip").is_empty());
+}
+
+#[tokio::test]
+async fn range_without_any_file_is_not_found() {
+    let (_server, scraper) = archive_stub(&["2024071"], Duration::ZERO).await;
+
+    let error = scraper.get_cm_bhavcopy_range(date(2024, 7, 15), date(2024, 7, 19)).await.unwrap_err();
+    assert!(matches!(error, NseScraperError::NotFound { symbol: None }), "{:?}", error);
+
+    // A weekend-only range makes no request and is simply empty
+    assert!(scraper.get_cm_bhavcopy_range(date(2024, 7, 20), date(2024, 7, 21)).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn range_limits_downloads_in_flight() {
+    let (server, scraper) = archive_stub(&[], Duration::from_millis(200)).await;
+
+    // 20 weekdays, downloaded at most eight at a time
+    scraper.get_cm_bhavcopy_range(date(2024, 7, 8), date(2024, 8, 2)).await.unwrap();
+
+    let downloads: Vec<_> = server.requests().into_iter().filter(|r| r.path != "/").collect();
+    assert_eq!(downloads.len(), 20);
+    let first = downloads[0].received_at;
+    let in_first_wave = downloads.iter().filter(|r| r.received_at - first < Duration::from_millis(150)).count();
+    assert_eq!(in_first_wave, 8);
+}
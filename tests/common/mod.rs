@@ -0,0 +1,257 @@
+// tests/common/mod.rs
+//! Helpers shared by the integration tests. Each test binary uses a subset.
+#![allow(dead_code)]
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::NaiveDate;
+use nse_scraper::NseScraper;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+pub fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// Contents of `tests/fixtures/<name>`
+pub fn fixture_text(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e))
+}
+
+/// `tests/fixtures/<name>` decoded from JSON
+pub fn fixture<T: DeserializeOwned>(name: &str) -> T {
+    serde_json::from_str(&fixture_text(name)).unwrap_or_else(|e| panic!("fixture {} should deserialize: {}", name, e))
+}
+
+/// A [`LoopbackServer`] that serves the home page and answers each of
+/// `routes` with its fixture, plus an unthrottled scraper pointed at it
+pub async fn nse_serving(routes: &[(&'static str, &'static str)]) -> (LoopbackServer, NseScraper) {
+    let routes: Vec<(&'static str, String)> = routes.iter().map(|(path, name)| (*path, fixture_text(name))).collect();
+    let server = LoopbackServer::start(move |request, _| {
+        if request.path == "/" {
+            return Reply::ok("<html></html>");
+        }
+        match routes.iter().find(|(path, _)| *path == request.path) {
+            Some((_, body)) => Reply::ok(body),
+            None => Reply::status(404),
+        }
+    })
+    .await;
+    let scraper = NseScraper::builder()
+        .base_url(&server.url)
+        .without_rate_limit()
+        .build()
+        .unwrap();
+    (server, scraper)
+}
+
+/// A request received by a [`LoopbackServer`]
+#[derive(Debug, Clone)]
+pub struct ReceivedRequest {
+    pub path: String,
+    /// Query string exactly as sent, without the leading `?`
+    pub raw_query: String,
+    /// Query parameters with percent-escapes decoded
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub received_at: Instant,
+}
+
+impl ReceivedRequest {
+    /// Decoded value of a query parameter
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A canned response
+#[derive(Debug, Clone)]
+pub struct Reply {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// How long the server waits before answering
+    pub delay: Duration,
+}
+
+impl Reply {
+    pub fn ok(body: &str) -> Self {
+        Reply {
+            status: 200,
+            headers: Vec::new(),
+            body: body.as_bytes().to_vec(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn bytes(body: Vec<u8>) -> Self {
+        Reply {
+            status: 200,
+            headers: Vec::new(),
+            body,
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Reply {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Minimal HTTP/1.1 server on 127.0.0.1 answering one request per connection.
+///
+/// The handler gets the request and how many earlier requests hit the same path.
+pub struct LoopbackServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<ReceivedRequest>>>,
+}
+
+impl LoopbackServer {
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&ReceivedRequest, usize) -> Reply + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests: Arc<Mutex<Vec<ReceivedRequest>>> = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(handler);
+
+        let log = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let log = Arc::clone(&log);
+                let handler = Arc::clone(&handler);
+                tokio::spawn(async move {
+                    let Some(request) = read_request(&mut stream).await else {
+                        return;
+                    };
+                    let reply = {
+                        let mut log = log.lock().unwrap();
+                        let hits = log.iter().filter(|r| r.path == request.path).count();
+                        log.push(request.clone());
+                        handler(&request, hits)
+                    };
+                    tokio::time::sleep(reply.delay).await;
+                    let _ = stream.write_all(&encode(&reply)).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        LoopbackServer { url, requests }
+    }
+
+    /// Every request received so far, in arrival order
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn requests_to(&self, path: &str) -> Vec<ReceivedRequest> {
+        self.requests().into_iter().filter(|r| r.path == path).collect()
+    }
+}
+
+async fn read_request(stream: &mut tokio::net::TcpStream) -> Option<ReceivedRequest> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let received_at = Instant::now();
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut lines = head.split("\r\n");
+    let target = lines.next()?.split_whitespace().nth(1)?;
+    let (path, raw_query) = target.split_once('?').unwrap_or((target, ""));
+    let query = raw_query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (percent_decode(key), percent_decode(value)))
+        .collect();
+    let headers = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    Some(ReceivedRequest {
+        path: path.to_string(),
+        raw_query: raw_query.to_string(),
+        query,
+        headers,
+        received_at,
+    })
+}
+
+/// Decode `%XX` escapes and `+` as sent in a form-encoded query
+fn percent_decode(encoded: &str) -> String {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn encode(reply: &Reply) -> Vec<u8> {
+    let mut head = format!(
+        "HTTP/1.1 {} Loopback\r\nContent-Length: {}\r\nConnection: close\r\n",
+        reply.status,
+        reply.body.len()
+    );
+    for (name, value) in &reply.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    let mut bytes = head.into_bytes();
+    bytes.extend_from_slice(&reply.body);
+    bytes
+}
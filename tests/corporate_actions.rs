@@ -0,0 +1,187 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::{date, fixture};
+use nse_scraper::models::{CorporateAction, CorporateActionKind, DailyData, HistoricalData};
+
+fn actions() -> Vec<CorporateAction> {
+    fixture("corporate_actions.json")
+}
+
+fn kinds() -> Vec<CorporateActionKind> {
+    actions().iter().map(CorporateAction::kind).collect()
+}
+
+fn action(subject: &str, ex_date: NaiveDate) -> CorporateAction {
+    CorporateAction {
+        symbol: "TEST".to_string(),
+        series: Some("EQ".to_string()),
+        face_value: Some(10.0),
+        subject: subject.to_string(),
+        ex_date: Some(ex_date),
+        record_date: Some(ex_date),
+        company: None,
+        isin: None,
+    }
+}
+
+fn day(timestamp: NaiveDate, close: f64, volume: u64) -> DailyData {
+    DailyData {
+        timestamp,
+        open: close,
+        high: close,
+        low: close,
+        close,
+        last: close,
+        prev_close: close,
+        volume,
+        value: close * volume as f64,
+        year_high: close,
+        year_low: close,
+        ..Default::default()
+    }
+}
+
+fn history() -> HistoricalData {
+    HistoricalData {
+        symbol: "TEST".to_string(),
+        data: vec![
+            day(date(2024, 7, 1), 200.0, 1000),
+            day(date(2024, 7, 2), 210.0, 1200),
+            day(date(2024, 7, 3), 104.0, 2600),
+        ],
+    }
+}
+
+#[test]
+fn parses_split() {
+    assert_eq!(kinds()[0], CorporateActionKind::Split { from: 10.0, to: 2.0 });
+    assert_eq!(
+        action("Face Value Split (Sub-Division) - From Rs 10/- Per Share To Re 1/- Per Share", date(2024, 7, 1)).kind(),
+        CorporateActionKind::Split { from: 10.0, to: 1.0 }
+    );
+}
+
+#[test]
+fn parses_bonus() {
+    assert_eq!(kinds()[1], CorporateActionKind::Bonus { new: 1.0, held: 1.0 });
+}
+
+#[test]
+fn parses_rights_with_premium_over_face_value() {
+    assert_eq!(
+        kinds()[2],
+        CorporateActionKind::Rights {
+            new: 1.0,
+            held: 15.0,
+            price: Some(1257.0)
+        }
+    );
+}
+
+#[test]
+fn parses_interim_final_and_special_dividends() {
+    let kinds = kinds();
+    assert_eq!(kinds[3], CorporateActionKind::Dividend { amount: 10.0 });
+    assert_eq!(kinds[4], CorporateActionKind::Dividend { amount: 28.0 });
+    assert_eq!(kinds[5], CorporateActionKind::Dividend { amount: 46.0 });
+    assert_eq!(kinds[6], CorporateActionKind::Dividend { amount: 0.5 });
+}
+
+#[test]
+fn currency_marker_must_start_a_word() {
+    // The "rs" in "shareholders" and the "re" in "record" precede a date, not an amount
+    assert_eq!(kinds()[7], CorporateActionKind::Dividend { amount: 6.25 });
+    assert_eq!(
+        action("Interim Dividend - Rs.9.50 Per Share", date(2024, 7, 1)).kind(),
+        CorporateActionKind::Dividend { amount: 9.5 }
+    );
+}
+
+#[test]
+fn unparseable_subject_is_other() {
+    let actions = actions();
+    assert_eq!(actions[8].kind(), CorporateActionKind::Other);
+    assert_eq!(actions[8].ex_date, None);
+    assert_eq!(action("Annual General Meeting", date(2024, 7, 1)).kind(), CorporateActionKind::Other);
+    assert_eq!(action("Bonus Issue", date(2024, 7, 1)).kind(), CorporateActionKind::Other);
+}
+
+#[test]
+fn adjusts_rows_before_a_split() {
+    let mut history = history();
+    history.adjust_for_corporate_actions(&[action("Face Value Split From Rs 10 To Rs 5", date(2024, 7, 3))], false);
+
+    let data = &history.data;
+    assert_eq!((data[0].close, data[1].close, data[2].close), (100.0, 105.0, 104.0));
+    assert_eq!((data[0].volume, data[1].volume, data[2].volume), (2000, 2400, 2600));
+    assert_eq!(data[0].adjustment_factor, Some(0.5));
+    assert_eq!(data[2].adjustment_factor, None);
+    // Traded value is unchanged by a split
+    assert_eq!(data[0].value, 200_000.0);
+}
+
+#[test]
+fn ex_date_at_the_first_row_adjusts_nothing() {
+    let mut history = history();
+    history.adjust_for_corporate_actions(&[action("Bonus 1:1", date(2024, 7, 1))], false);
+
+    assert_eq!(history.data[0].close, 200.0);
+    assert!(history.data.iter().all(|d| d.adjustment_factor.is_none()));
+}
+
+#[test]
+fn ex_date_after_the_last_row_adjusts_every_row() {
+    let mut history = history();
+    history.adjust_for_corporate_actions(&[action("Bonus 1:1", date(2024, 7, 10))], false);
+
+    let closes: Vec<f64> = history.data.iter().map(|d| d.close).collect();
+    assert_eq!(closes, [100.0, 105.0, 52.0]);
+    assert!(history.data.iter().all(|d| d.adjustment_factor == Some(0.5)));
+}
+
+#[test]
+fn dividends_adjust_only_when_asked() {
+    let dividend = [action("Final Dividend - Rs 10.50 Per Share", date(2024, 7, 3))];
+
+    let mut history_without = history();
+    history_without.adjust_for_corporate_actions(&dividend, false);
+    assert_eq!(history_without.data[1].close, 210.0);
+
+    let mut history_with = history();
+    history_with.adjust_for_corporate_actions(&dividend, true);
+    // (210 - 10.5) / 210 = 0.95
+    assert!((history_with.data[1].close - 199.5).abs() < 1e-9);
+    assert_eq!(history_with.data[1].volume, 1200);
+}
+
+#[test]
+fn actions_sharing_an_ex_date_use_the_unadjusted_close() {
+    let bonus = action("Bonus 1:1", date(2024, 7, 3));
+    let dividend = action("Final Dividend - Rs 10.50 Per Share", date(2024, 7, 3));
+
+    for actions in [[bonus.clone(), dividend.clone()], [dividend, bonus]] {
+        let mut history = history();
+        history.adjust_for_corporate_actions(&actions, true);
+        // 210 * 0.5 for the bonus * (210 - 10.5) / 210 for the dividend
+        assert!((history.data[1].close - 99.75).abs() < 1e-9, "{}", history.data[1].close);
+        assert!((history.data[1].adjustment_factor.unwrap() - 0.475).abs() < 1e-12);
+        assert_eq!(history.data[1].volume, 2400);
+    }
+}
+
+#[test]
+fn rights_and_dividends_after_the_last_row_are_skipped() {
+    let mut history = history();
+    history.adjust_for_corporate_actions(
+        &[
+            action("Final Dividend - Rs 10.50 Per Share", date(2024, 7, 10)),
+            action("Rights 1:4 @ Premium Rs 40/-", date(2024, 7, 10)),
+        ],
+        true,
+    );
+
+    let closes: Vec<f64> = history.data.iter().map(|d| d.close).collect();
+    assert_eq!(closes, [200.0, 210.0, 104.0]);
+    assert!(history.data.iter().all(|d| d.adjustment_factor.is_none()));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::{date, fixture, nse_serving};
+use nse_scraper::models::{DailyData, DealRecords, DealSide, HistoricalData, LargeDeals};
+use nse_scraper::Symbol;
+
+fn day(timestamp: NaiveDate, volume: u64) -> DailyData {
+    DailyData {
+        timestamp,
+        volume,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn parses_live_snapshot() {
+    let deals: LargeDeals = fixture("large_deals.json");
+    assert_eq!(deals.as_on_date, Some(date(2024, 7, 8)));
+    assert!(deals.block_deals.is_empty());
+
+    let bulk = &deals.bulk_deals[0];
+    assert_eq!(bulk.client_name, "GRAVITON RESEARCH CAPITAL LLP");
+    assert_eq!(bulk.side, DealSide::Buy);
+    assert_eq!(bulk.quantity, 612345);
+    assert_eq!(bulk.price, 412.35);
+
+    assert_eq!(deals.short_selling[0].symbol, "INFY");
+    assert_eq!(deals.short_selling[0].quantity, 12000);
+}
+
+#[test]
+fn parses_historical_deals() {
+    let records: DealRecords = fixture("bulk_deals_history.json");
+    assert_eq!(records.data.len(), 3);
+    assert_eq!(records.data[1].side, DealSide::Sell);
+    assert_eq!(records.data[1].remarks, None);
+    assert_eq!(records.data[0].value(), 2500000.0 * 3160.2);
+}
+
+#[test]
+fn joins_deals_onto_history() {
+    let records: DealRecords = fixture("bulk_deals_history.json");
+    let history = HistoricalData {
+        symbol: "RELIANCE".to_string(),
+        data: vec![day(date(2024, 7, 1), 4_000_000), day(date(2024, 7, 2), 10_000_000), day(date(2024, 7, 3), 5_000_000)],
+    };
+
+    let joined = history.join_deals(&records.data);
+    assert_eq!(joined.len(), 1);
+    assert_eq!(joined[0].day.timestamp, date(2024, 7, 2));
+    assert_eq!(joined[0].deals.len(), 2);
+    assert_eq!(joined[0].bought_quantity(), 2_500_000);
+    assert_eq!(joined[0].sold_quantity(), 2_000_000);
+    assert_eq!(joined[0].volume_share(), Some(25.0));
+}
+
+#[tokio::test]
+async fn deal_requests_carry_symbol_and_windowed_dates() {
+    let (server, scraper) = nse_serving(&[
+        ("/api/snapshot-capital-market-largedeal", "large_deals.json"),
+        ("/api/historical/bulk-deals", "bulk_deals_history.json"),
+        ("/api/historical/block-deals", "bulk_deals_history.json"),
+        ("/api/historical/short-selling", "short_selling_history.json"),
+    ])
+    .await;
+    let reliance = Symbol::new("RELIANCE").unwrap();
+
+    scraper.get_large_deals().await.unwrap();
+    scraper.get_bulk_deals(Some(&reliance), date(2023, 1, 1), date(2024, 7, 3)).await.unwrap();
+    scraper.get_block_deals(None, date(2024, 7, 1), date(2024, 7, 3)).await.unwrap();
+    let sales = scraper.get_short_selling(None, date(2024, 7, 1), date(2024, 7, 2)).await.unwrap();
+
+    assert_eq!(server.requests_to("/api/snapshot-capital-market-largedeal")[0].raw_query, "");
+
+    // Longer than a year: one request per window
+    let mut bulk: Vec<String> = server.requests_to("/api/historical/bulk-deals").iter().map(|r| r.raw_query.clone()).collect();
+    bulk.sort();
+    assert_eq!(
+        bulk,
+        [
+            "from=01-01-2023&to=31-12-2023&symbol=RELIANCE",
+            "from=01-01-2024&to=03-07-2024&symbol=RELIANCE",
+        ]
+    );
+
+    assert_eq!(server.requests_to("/api/historical/block-deals")[0].raw_query, "from=01-07-2024&to=03-07-2024");
+    assert_eq!(server.requests_to("/api/historical/short-selling")[0].raw_query, "from=01-07-2024&to=02-07-2024");
+    // Sorted by date
+    assert_eq!(sales.iter().map(|s| s.quantity).collect::<Vec<_>>(), [8500, 12000]);
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::date;
+use nse_scraper::delivery;
+use nse_scraper::models::{CorporateAction, DailyData, HistoricalData, TimeFrame};
+
+const BHAVDATA: &str = include_str!("fixtures/sec_bhavdata_full_02072024.csv");
+const MTO: &str = include_str!("fixtures/MTO_03072024.DAT");
+
+fn day(timestamp: NaiveDate, volume: u64) -> DailyData {
+    DailyData {
+        timestamp,
+        open: 100.0,
+        high: 101.0,
+        low: 99.0,
+        close: 100.5,
+        volume,
+        ..Default::default()
+    }
+}
+
+fn delivery_rows() -> Vec<delivery::DeliveryRow> {
+    let mut rows = delivery::parse_bhavdata_csv(BHAVDATA.as_bytes()).unwrap();
+    rows.extend(delivery::parse_mto(MTO.as_bytes()).unwrap());
+    rows
+}
+
+#[test]
+fn parses_security_bhavdata() {
+    let rows = delivery::parse_bhavdata_csv(BHAVDATA.as_bytes()).unwrap();
+    assert_eq!(rows.len(), 3);
+
+    let infy = &rows[0];
+    assert_eq!(infy.symbol, "INFY");
+    assert_eq!(infy.date, date(2024, 7, 2));
+    assert_eq!(infy.traded_qty, 4000000);
+    assert_eq!(infy.delivery_qty, Some(2000000));
+    assert_eq!(infy.delivery_pct, Some(50.0));
+
+    // Block deal series carry no delivery figures
+    let block = &rows[1];
+    assert_eq!(block.series, "BL");
+    assert_eq!(block.delivery_qty, None);
+    assert_eq!(block.delivery_pct, None);
+}
+
+#[test]
+fn parses_mto_file() {
+    let rows = delivery::parse_mto(MTO.as_bytes()).unwrap();
+    assert_eq!(rows.len(), 3);
+    assert!(rows.iter().all(|r| r.date == date(2024, 7, 3)));
+
+    let reliance = rows.iter().find(|r| r.symbol == "RELIANCE" && r.series == "EQ").unwrap();
+    assert_eq!(reliance.traded_qty, 4000000);
+    assert_eq!(reliance.delivery_qty, Some(1000000));
+    assert_eq!(reliance.delivery_pct, Some(25.0));
+}
+
+#[test]
+fn merges_into_daily_data() {
+    let rows = delivery_rows();
+    let mut history = HistoricalData {
+        symbol: "RELIANCE".to_string(),
+        data: vec![day(date(2024, 7, 2), 6000000), day(date(2024, 7, 3), 4000000), day(date(2024, 7, 4), 5000000)],
+    };
+    history.merge_delivery(&rows, "EQ");
+
+    assert_eq!(history.data[0].delivery_qty, Some(3600000));
+    assert_eq!(history.data[1].delivery_pct, Some(25.0));
+    assert_eq!(history.data[2].delivery_qty, None);
+}
+
+#[test]
+fn weekly_delivery_is_weighted_by_volume() {
+    let rows = delivery_rows();
+    let mut history = HistoricalData {
+        symbol: "INFY".to_string(),
+        data: vec![day(date(2024, 7, 2), 4000000), day(date(2024, 7, 3), 1000000)],
+    };
+    history.merge_delivery(&rows, "EQ");
+
+    let weekly = history.to_time_frame(TimeFrame::Weekly);
+    assert_eq!(weekly.data.len(), 1);
+    assert_eq!(weekly.data[0].delivery_qty, Some(2300000));
+    // 2.3M of 5M traded, not the 40% mean of the daily figures
+    assert!((weekly.data[0].delivery_pct.unwrap() - 46.0).abs() < 1e-9);
+
+    // A day without delivery data leaves the period without a figure
+    history.data.push(day(date(2024, 7, 4), 1000000));
+    let weekly = history.to_time_frame(TimeFrame::Weekly);
+    assert_eq!(weekly.data[0].delivery_qty, None);
+    assert_eq!(weekly.data[0].delivery_pct, None);
+}
+
+#[test]
+fn delivery_survives_split_adjustment() {
+    let rows = delivery_rows();
+    let mut history = HistoricalData {
+        symbol: "INFY".to_string(),
+        data: vec![day(date(2024, 7, 2), 4000000), day(date(2024, 7, 3), 1000000)],
+    };
+    history.merge_delivery(&rows, "EQ");
+
+    let split: CorporateAction = serde_json::from_str(
+        r#"{"symbol": "INFY", "faceVal": "5", "subject": "Face Value Split From Rs 10 To Rs 5", "exDate": "03-Jul-2024"}"#,
+    )
+    .unwrap();
+    history.adjust_for_corporate_actions(&[split], false);
+
+    assert_eq!(history.data[0].volume, 8000000);
+    assert_eq!(history.data[0].delivery_qty, Some(4000000));
+    assert_eq!(history.data[0].delivery_pct, Some(50.0));
+
+    let weekly = history.to_time_frame(TimeFrame::Weekly);
+    assert_eq!(weekly.data[0].delivery_qty, Some(4300000));
+    assert!((weekly.data[0].delivery_pct.unwrap() - 4.3 / 9.0 * 100.0).abs() < 1e-9);
+}
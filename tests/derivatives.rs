@@ -0,0 +1,68 @@
+mod common;
+
+use common::{date, fixture};
+use nse_scraper::models::derivatives;
+use nse_scraper::models::{ContractKind, DerivativeQuote};
+use nse_scraper::pricing::OptionType;
+
+fn reliance() -> DerivativeQuote {
+    fixture("quote_derivative_reliance.json")
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+}
+
+#[test]
+fn futures_are_ordered_by_expiry_with_basis() {
+    let futures = reliance().futures(date(2024, 7, 5));
+    assert_eq!(futures.len(), 2);
+
+    let near = &futures[0];
+    assert_eq!(near.kind, ContractKind::StockFuture);
+    assert_eq!(near.expiry_date, date(2024, 7, 25));
+    assert_eq!(near.lot_size, Some(250));
+    assert_eq!(near.open_interest, Some(81234.0));
+    assert_eq!(near.change_in_open_interest, Some(-2310.0));
+    assert_close(near.basis, 12.0);
+    assert_close(futures[1].basis, 36.5);
+}
+
+#[test]
+fn cost_of_carry_is_annualised_over_calendar_days() {
+    let futures = reliance().futures(date(2024, 7, 5));
+    // 20 days to the July expiry: ln(3012 / 3000) * 365 / 20
+    assert_close(futures[0].cost_of_carry.unwrap(), (3012.0f64 / 3000.0).ln() * 365.0 / 20.0);
+    assert_close(futures[1].cost_of_carry.unwrap(), (3036.5f64 / 3000.0).ln() * 365.0 / 55.0);
+}
+
+#[test]
+fn cost_of_carry_needs_time_left() {
+    let expiry = date(2024, 7, 25);
+    assert_close(derivatives::cost_of_carry(3000.0, 3012.0, date(2024, 7, 24), expiry).unwrap(), (3012.0f64 / 3000.0).ln() * 365.0);
+    // On the expiry day, and after it, there is no time to annualise over
+    assert_eq!(derivatives::cost_of_carry(3000.0, 3012.0, expiry, expiry), None);
+    assert_eq!(derivatives::cost_of_carry(3000.0, 3012.0, date(2024, 7, 26), expiry), None);
+
+    let on_expiry = reliance().futures(expiry);
+    assert_close(on_expiry[0].basis, 12.0);
+    assert_eq!(on_expiry[0].cost_of_carry, None);
+}
+
+#[test]
+fn cost_of_carry_rejects_non_positive_prices() {
+    let (as_of, expiry) = (date(2024, 7, 5), date(2024, 7, 25));
+    assert_eq!(derivatives::cost_of_carry(0.0, 3012.0, as_of, expiry), None);
+    assert_eq!(derivatives::cost_of_carry(3000.0, 0.0, as_of, expiry), None);
+    assert_close(derivatives::cost_of_carry(3000.0, 3000.0, as_of, expiry).unwrap(), 0.0);
+}
+
+#[test]
+fn options_are_separated_from_futures() {
+    let options = reliance().options();
+    assert_eq!(options.len(), 1);
+    assert_eq!(options[0].kind, ContractKind::StockOption);
+    assert_eq!(options[0].option_type, OptionType::Call);
+    assert_eq!(options[0].strike_price, 3100.0);
+    assert_eq!(options[0].implied_volatility, Some(21.4));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{LoopbackServer, Reply};
+use nse_scraper::{Endpoint, NseScraper, NseScraperError, RetryPolicy, Symbol};
+
+/// A scraper whose every API call is answered with `body`
+async fn answering(body: &'static str) -> (LoopbackServer, NseScraper) {
+    let server = LoopbackServer::start(move |request, _| match request.path.as_str() {
+        "/" => Reply::ok("<html></html>"),
+        _ => Reply::ok(body),
+    })
+    .await;
+    let scraper = NseScraper::builder()
+        .base_url(&server.url)
+        .without_rate_limit()
+        .build()
+        .unwrap();
+    (server, scraper)
+}
+
+#[tokio::test]
+async fn missing_vix_is_reported_as_missing_data() {
+    let (_server, scraper) = answering(r#"{"data": [], "timestamp": "05-Jul-2024 15:30"}"#).await;
+
+    let error = scraper.get_india_vix().await.unwrap_err();
+    assert!(matches!(
+        error,
+        NseScraperError::MissingData {
+            endpoint: Endpoint::AllIndices,
+            what: "INDIA VIX"
+        }
+    ));
+}
+
+async fn quote_error(body: &'static str) -> NseScraperError {
+    let (_server, scraper) = answering(body).await;
+    scraper.get_quote(&Symbol::new("NOSUCH").unwrap()).await.unwrap_err()
+}
+
+#[tokio::test]
+async fn empty_payloads_mean_an_unknown_symbol() {
+    for body in ["", "{}", "[]", "null"] {
+        let error = quote_error(body).await;
+        assert!(matches!(&error, NseScraperError::InvalidSymbol(s) if s == "NOSUCH"), "{:?}", error);
+    }
+}
+
+#[tokio::test]
+async fn error_field_naming_the_symbol_means_an_unknown_symbol() {
+    let error = quote_error(r#"{"error": "Symbol not found"}"#).await;
+    assert!(matches!(error, NseScraperError::InvalidSymbol(_)), "{:?}", error);
+
+    let error = quote_error(r#"{"message": "Invalid symbol NOSUCH"}"#).await;
+    assert!(matches!(error, NseScraperError::InvalidSymbol(_)), "{:?}", error);
+}
+
+#[tokio::test]
+async fn no_data_found_is_not_an_unknown_symbol() {
+    let error = quote_error(r#"{"message": "No data found"}"#).await;
+    assert!(matches!(error, NseScraperError::Decode { endpoint: Endpoint::Quote, .. }), "{:?}", error);
+}
+
+#[tokio::test]
+async fn marker_text_elsewhere_in_the_payload_is_not_an_unknown_symbol() {
+    let error = quote_error(r#"{"info": {"companyName": "No Data Found Ltd"}, "note": "invalid symbol"}"#).await;
+    assert!(matches!(error, NseScraperError::Decode { endpoint: Endpoint::Quote, .. }), "{:?}", error);
+}
+
+#[tokio::test]
+async fn rate_limiting_is_visible_through_exhausted_retries() {
+    let server = LoopbackServer::start(|request, _| match request.path.as_str() {
+        "/" => Reply::ok("<html></html>"),
+        _ => Reply::status(429),
+    })
+    .await;
+    let scraper = NseScraper::builder()
+        .base_url(&server.url)
+        .retry_policy(RetryPolicy {
+            max_attempts: 2,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(10),
+            jitter: 0.0,
+        })
+        .without_rate_limit()
+        .build()
+        .unwrap();
+
+    let error = scraper.get_fii_dii_activity().await.unwrap_err();
+
+    assert!(matches!(error, NseScraperError::RetriesExhausted { attempts: 2, .. }), "{:?}", error);
+    assert!(error.is_rate_limited());
+    assert!(matches!(error.root(), NseScraperError::RateLimited { retry_after: None }));
+}
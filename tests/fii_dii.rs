@@ -0,0 +1,53 @@
+mod common;
+
+use common::{date, fixture, nse_serving};
+use nse_scraper::fii_dii::{self, FiiDiiActivity, InvestorCategory};
+use nse_scraper::NseScraperError;
+
+fn live() -> Vec<FiiDiiActivity> {
+    fixture("fii_dii_trade_react.json")
+}
+
+#[test]
+fn parses_live_activity() {
+    let rows = live();
+    let dii = &rows[0];
+    assert_eq!(dii.investor_category(), InvestorCategory::Dii);
+    assert_eq!(dii.date, date(2024, 7, 5));
+    assert_eq!(dii.buy_value, 12345.67);
+    assert_eq!(dii.net_value, -659.45);
+    assert_eq!(rows[1].investor_category(), InvestorCategory::Fii);
+}
+
+#[test]
+fn pairs_categories_by_date() {
+    // A stored earlier day followed by the latest one
+    let mut rows: Vec<FiiDiiActivity> = live()
+        .into_iter()
+        .map(|row| FiiDiiActivity { date: date(2024, 7, 4), ..row })
+        .collect();
+    rows.extend(live());
+
+    let days = fii_dii::by_date(&rows);
+    assert_eq!(days.iter().map(|d| d.date).collect::<Vec<_>>(), [date(2024, 7, 4), date(2024, 7, 5)]);
+    assert_eq!(days[0].fii.as_ref().unwrap().net_value, 1242.29);
+    assert_eq!(days[1].dii.as_ref().unwrap().net_value, -659.45);
+}
+
+#[tokio::test]
+async fn fetches_live_activity() {
+    let (server, scraper) = nse_serving(&[("/api/fiidiiTradeReact", "fii_dii_trade_react.json")]).await;
+
+    let rows = scraper.get_fii_dii_activity().await.unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(server.requests_to("/api/fiidiiTradeReact")[0].raw_query, "");
+}
+
+#[tokio::test]
+async fn history_is_unsupported() {
+    let (server, scraper) = nse_serving(&[]).await;
+
+    let error = scraper.get_fii_dii_history(date(2024, 7, 1), date(2024, 7, 5)).await.unwrap_err();
+    assert!(matches!(error, NseScraperError::Unsupported(_)), "{:?}", error);
+    assert!(server.requests().is_empty());
+}
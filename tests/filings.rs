@@ -0,0 +1,92 @@
+mod common;
+
+use common::{date, fixture, nse_serving};
+use nse_scraper::models::{Announcement, BoardMeeting, FilingCategory, FilingCursor, FilingFilter, FinancialResult, ResultPeriod};
+use nse_scraper::Symbol;
+
+fn announcements() -> Vec<Announcement> {
+    fixture("corporate_announcements.json")
+}
+
+#[test]
+fn parses_announcements() {
+    let announcements = announcements();
+    let outcome = &announcements[0];
+    assert_eq!(outcome.company.as_deref(), Some("Infosys Limited"));
+    assert_eq!(outcome.broadcast_time.unwrap().to_rfc3339(), "2024-07-18T16:15:12+05:30");
+    assert!(outcome.attachment_url.as_deref().unwrap().ends_with("_Outcome.pdf"));
+
+    let categories: Vec<_> = announcements.iter().map(|a| a.category()).collect();
+    assert_eq!(
+        categories,
+        [FilingCategory::BoardMeeting, FilingCategory::Dividend, FilingCategory::AnalystMeet]
+    );
+}
+
+#[test]
+fn parses_board_meetings_and_results() {
+    let meetings: Vec<BoardMeeting> = fixture("board_meetings.json");
+    assert_eq!(meetings[0].meeting_date.unwrap().to_string(), "2024-07-11");
+    assert_eq!(meetings[0].category(), FilingCategory::FinancialResults);
+    assert_eq!(meetings[1].category(), FilingCategory::FundRaising);
+
+    let results: Vec<FinancialResult> = fixture("financial_results.json");
+    let tcs = &results[0];
+    assert!(!tcs.is_audited());
+    assert!(tcs.is_consolidated());
+    assert_eq!(tcs.period_end.unwrap().to_string(), "2024-06-30");
+    assert_eq!(tcs.filing_time.unwrap().to_rfc3339(), "2024-07-11T16:02:00+05:30");
+}
+
+#[test]
+fn cursor_returns_only_unseen_filings() {
+    let mut cursor = FilingCursor::new();
+    let first = cursor.advance(announcements());
+    // Oldest first
+    assert_eq!(first.len(), 3);
+    assert_eq!(first[0].category(), FilingCategory::AnalystMeet);
+    assert_eq!(cursor.last_seen().unwrap().to_rfc3339(), "2024-07-18T16:15:12+05:30");
+
+    assert!(cursor.advance(announcements()).is_empty());
+
+    // A late filing in the same second as the last one seen is still new
+    let mut late = announcements()[0].clone();
+    late.subject = "Press Release".to_string();
+    let mut next = announcements();
+    next.push(late);
+    let new = cursor.advance(next);
+    assert_eq!(new.len(), 1);
+    assert_eq!(new[0].category(), FilingCategory::PressRelease);
+
+    // The cursor survives a round trip through storage
+    let stored = serde_json::to_string(&cursor).unwrap();
+    let mut restored: FilingCursor = serde_json::from_str(&stored).unwrap();
+    assert!(restored.advance(announcements()).is_empty());
+}
+
+#[tokio::test]
+async fn filing_requests_carry_the_filter() {
+    let (server, scraper) = nse_serving(&[
+        ("/api/corporate-announcements", "corporate_announcements.json"),
+        ("/api/corporate-board-meetings", "board_meetings.json"),
+        ("/api/corporates-financial-results", "financial_results.json"),
+    ])
+    .await;
+    let filter = FilingFilter::default()
+        .symbol(Symbol::new("infy").unwrap())
+        .date_range(date(2024, 7, 1), date(2024, 7, 18));
+
+    assert_eq!(scraper.get_announcements(&filter).await.unwrap().len(), 3);
+    scraper.get_board_meetings(&FilingFilter::default()).await.unwrap();
+    scraper.get_financial_results(&filter, ResultPeriod::HalfYearly).await.unwrap();
+
+    let announcements = &server.requests_to("/api/corporate-announcements")[0];
+    assert_eq!(announcements.raw_query, "index=equities&symbol=INFY&from_date=01-07-2024&to_date=18-07-2024");
+
+    // Without a symbol or dates NSE returns its latest filings
+    assert_eq!(server.requests_to("/api/corporate-board-meetings")[0].raw_query, "index=equities");
+
+    let results = &server.requests_to("/api/corporates-financial-results")[0];
+    assert_eq!(results.param("period"), Some("Half-Yearly"));
+    assert_eq!(results.param("symbol"), Some("INFY"));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use chrono::{Duration, NaiveDate};
+use common::{LoopbackServer, Reply, date};
+use nse_scraper::{NseScraper, Symbol};
+use serde_json::json;
+
+const API: &str = "/api/historical/cm/equity";
+
+fn row(day: NaiveDate) -> serde_json::Value {
+    json!({
+        "CH_TIMESTAMP": day.format("%Y-%m-%d").to_string(),
+        "CH_OPENING_PRICE": 100, "CH_TRADE_HIGH_PRICE": 105, "CH_TRADE_LOW_PRICE": 95,
+        "CH_CLOSING_PRICE": 102, "CH_LAST_TRADED_PRICE": 102, "CH_PREVIOUS_CLS_PRICE": 100,
+        "CH_TOT_TRADED_QTY": 1000, "CH_TOT_TRADED_VAL": 102000,
+        "CH_52WEEK_HIGH_PRICE": 120, "CH_52WEEK_LOW_PRICE": 80
+    })
+}
+
+/// Answers each window with its last and first day, newest first, plus the
+/// day before the window when that is still in the range, as NSE sometimes does
+async fn nse_stub(range_start: NaiveDate) -> LoopbackServer {
+    LoopbackServer::start(move |request, _| {
+        if request.path != API {
+            return Reply::ok("<html></html>");
+        }
+        let parse = |name| NaiveDate::parse_from_str(request.param(name).unwrap(), "%d-%m-%Y").unwrap();
+        let (from, to) = (parse("from"), parse("to"));
+
+        let mut rows = vec![row(to), row(from)];
+        if from > range_start {
+            rows.push(row(from - Duration::days(1)));
+        }
+        Reply::ok(&json!({ "symbol": "TCS", "data": rows }).to_string())
+    })
+    .await
+}
+
+async fn windows_requested(from: NaiveDate, to: NaiveDate) -> Vec<(String, String)> {
+    let server = nse_stub(from).await;
+    let scraper = NseScraper::builder().base_url(&server.url).without_rate_limit().build().unwrap();
+    scraper.get_historical_range(&Symbol::new("TCS").unwrap(), "EQ", from, to).await.unwrap();
+
+    let mut windows: Vec<(String, String)> = server
+        .requests_to(API)
+        .iter()
+        .map(|r| (r.param("from").unwrap().to_string(), r.param("to").unwrap().to_string()))
+        .collect();
+    windows.sort_by_key(|(from, _)| NaiveDate::parse_from_str(from, "%d-%m-%Y").unwrap());
+    windows
+}
+
+fn window(from: &str, to: &str) -> (String, String) {
+    (from.to_string(), to.to_string())
+}
+
+#[tokio::test]
+async fn a_single_day_is_one_window() {
+    let windows = windows_requested(date(2024, 3, 1), date(2024, 3, 1)).await;
+    assert_eq!(windows, [window("01-03-2024", "01-03-2024")]);
+}
+
+#[tokio::test]
+async fn exactly_one_window_of_days_is_not_split() {
+    // 365 days, 2022 not being a leap year
+    let windows = windows_requested(date(2022, 1, 1), date(2022, 12, 31)).await;
+    assert_eq!(windows, [window("01-01-2022", "31-12-2022")]);
+}
+
+#[tokio::test]
+async fn one_day_over_the_window_starts_another() {
+    let windows = windows_requested(date(2022, 1, 1), date(2023, 1, 1)).await;
+    assert_eq!(windows, [window("01-01-2022", "31-12-2022"), window("01-01-2023", "01-01-2023")]);
+}
+
+#[tokio::test]
+async fn long_ranges_split_into_adjacent_windows() {
+    let windows = windows_requested(date(2021, 1, 1), date(2023, 12, 31)).await;
+    assert_eq!(
+        windows,
+        [
+            window("01-01-2021", "31-12-2021"),
+            window("01-01-2022", "31-12-2022"),
+            window("01-01-2023", "31-12-2023"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn merges_overlapping_windows_sorted_without_duplicates() {
+    let (from, to) = (date(2021, 1, 1), date(2023, 12, 31));
+    let server = nse_stub(from).await;
+    let scraper = NseScraper::builder().base_url(&server.url).without_rate_limit().build().unwrap();
+
+    let history = scraper.get_historical_range(&Symbol::new("TCS").unwrap(), "EQ", from, to).await.unwrap();
+
+    let days: Vec<NaiveDate> = history.data.iter().map(|d| d.timestamp).collect();
+    assert_eq!(
+        days,
+        [
+            date(2021, 1, 1),
+            date(2021, 12, 31),
+            date(2022, 1, 1),
+            date(2022, 12, 31),
+            date(2023, 1, 1),
+            date(2023, 12, 31),
+        ]
+    );
+    assert_eq!(history.symbol, "TCS");
+}
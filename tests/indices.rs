@@ -0,0 +1,108 @@
+mod common;
+
+use common::{date, fixture, nse_serving};
+use nse_scraper::models::{IndexHistory, IndexStocks, IndiaVix, IndicesData, TimeFrame, VixHistory};
+
+#[test]
+fn index_history_becomes_daily_rows() {
+    let history: IndexHistory = fixture("index_history_nifty.json");
+    let history = history.into_historical("NIFTY 50");
+
+    assert_eq!(history.symbol, "NIFTY 50");
+    let dates: Vec<_> = history.data.iter().map(|d| d.timestamp).collect();
+    assert_eq!(dates, [date(2024, 1, 1), date(2024, 1, 2), date(2024, 1, 3)]);
+
+    let second = &history.data[1];
+    assert_eq!(second.open, 21751.35);
+    assert_eq!(second.prev_close, 21741.9);
+    assert_eq!(second.volume, 309123456);
+    assert_eq!(second.value, 287654321098.5);
+    assert_eq!(second.year_high, 21834.35);
+
+    // No turnover record for the third day
+    assert_eq!(history.data[2].volume, 0);
+
+    let weekly = history.to_time_frame(TimeFrame::Weekly);
+    assert_eq!(weekly.data.len(), 1);
+    assert_eq!(weekly.data[0].open, 21727.75);
+    assert_eq!(weekly.data[0].close, 21665.8);
+}
+
+#[test]
+fn constituent_weights_from_ffmc() {
+    let stocks: IndexStocks = fixture("equity_stock_indices_nifty.json");
+    let constituents = stocks.constituents();
+
+    let symbols: Vec<_> = constituents.iter().map(|c| c.symbol.as_str()).collect();
+    assert_eq!(symbols, ["HDFCBANK", "RELIANCE", "INFY"]);
+    assert_eq!(constituents[0].weight, Some(40.0));
+    assert_eq!(constituents[2].weight, Some(20.0));
+    assert_eq!(constituents[2].ffmc, Some(5000.0));
+}
+
+#[tokio::test]
+async fn index_history_requests_the_normalised_name_and_dates() {
+    let (server, scraper) = nse_serving(&[("/api/historical/indicesHistory", "index_history_nifty.json")]).await;
+
+    let history = scraper.get_index_history(" nifty   50", date(2024, 1, 1), date(2024, 1, 3)).await.unwrap();
+    assert_eq!(history.symbol, "NIFTY 50");
+    assert_eq!(history.data.len(), 3);
+
+    let request = &server.requests_to("/api/historical/indicesHistory")[0];
+    assert_eq!(request.param("indexType"), Some("NIFTY 50"));
+    assert_eq!(request.param("from"), Some("01-01-2024"));
+    assert_eq!(request.param("to"), Some("03-01-2024"));
+}
+
+#[tokio::test]
+async fn constituents_request_the_index() {
+    let (server, scraper) = nse_serving(&[("/api/equity-stockIndices", "equity_stock_indices_nifty.json")]).await;
+
+    let constituents = scraper.get_index_constituents("nifty 50").await.unwrap();
+    assert_eq!(constituents.len(), 3);
+
+    let request = &server.requests_to("/api/equity-stockIndices")[0];
+    assert_eq!(request.param("index"), Some("NIFTY 50"));
+}
+
+#[test]
+fn vix_history_becomes_daily_rows() {
+    let history: VixHistory = fixture("vix_history.json");
+    let history = history.into_historical();
+
+    assert_eq!(history.symbol, "INDIA VIX");
+    assert_eq!(history.data[0].timestamp, date(2024, 1, 1));
+    assert_eq!(history.data[0].close, 14.9);
+    // First row has no previous close and falls back to its open
+    assert_eq!(history.data[0].prev_close, 14.5);
+    assert_eq!(history.data[1].prev_close, 14.9);
+    assert_eq!(history.data[1].year_low, 13.9);
+}
+
+#[test]
+fn current_vix_from_all_indices() {
+    let indices: IndicesData = fixture("all_indices.json");
+    let vix = IndiaVix::from_indices(&indices).unwrap();
+
+    assert_eq!(vix.last, 13.71);
+    assert_eq!(vix.change, 0.46);
+    assert_eq!(vix.timestamp.unwrap().date_naive(), date(2024, 3, 15));
+}
+
+#[tokio::test]
+async fn requests_current_and_historical_vix() {
+    let (server, scraper) = nse_serving(&[
+        ("/api/allIndices", "all_indices.json"),
+        ("/api/historical/vixhistory", "vix_history.json"),
+    ])
+    .await;
+
+    assert_eq!(scraper.get_india_vix().await.unwrap().last, 13.71);
+    let history = scraper.get_vix_history(date(2024, 1, 1), date(2024, 1, 31)).await.unwrap();
+    assert_eq!(history.symbol, "INDIA VIX");
+
+    let request = &server.requests_to("/api/historical/vixhistory")[0];
+    assert_eq!(request.param("from"), Some("01-01-2024"));
+    assert_eq!(request.param("to"), Some("31-01-2024"));
+    assert_eq!(server.requests_to("/api/allIndices").len(), 1);
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::{date, fixture, nse_serving};
+use nse_scraper::models::{FilingCursor, FilingFilter, InsiderSide, InsiderTrades, PersonCategory, SastDisclosures};
+use nse_scraper::Symbol;
+
+#[test]
+fn parses_pit_disclosures() {
+    let trades: InsiderTrades = fixture("insider_trading.json");
+    let buy = &trades.data[0];
+    assert_eq!(buy.person, "ANAND FAMILY TRUST");
+    assert_eq!(buy.category(), PersonCategory::PromoterGroup);
+    assert!(buy.category().is_promoter());
+    assert_eq!(buy.side(), InsiderSide::Acquisition);
+    assert_eq!(buy.securities, Some(150000));
+    assert_eq!((buy.shares_before, buy.shares_after), (Some(12500000), Some(12650000)));
+    assert!((buy.holding_change_pct().unwrap() - 0.12).abs() < 1e-9);
+    assert_eq!(buy.mode.as_deref(), Some("Market Purchase"));
+    assert_eq!(buy.intimation_date.unwrap().to_string(), "2024-07-04");
+    assert_eq!(buy.disclosed_at.unwrap().to_rfc3339(), "2024-07-05T18:22:00+05:30");
+
+    let pledge = &trades.data[1];
+    assert_eq!(pledge.category(), PersonCategory::Director);
+    assert_eq!(pledge.side(), InsiderSide::PledgeRevoked);
+    assert_eq!(pledge.value, None);
+    assert_eq!(pledge.holding_change_pct(), None);
+    assert_eq!(pledge.intimation_date, None);
+}
+
+#[test]
+fn parses_sast_disclosures() {
+    let disclosures: SastDisclosures = fixture("sast_disclosures.json");
+    let sale = &disclosures.data[0];
+    assert!(!sale.is_promoter());
+    assert_eq!(sale.side(), InsiderSide::Disposal);
+    assert_eq!(sale.shares_sold, Some(3_000_000));
+    assert_eq!(sale.holding_pct_after, Some(4.83));
+
+    // No transaction type: the side follows the share counts
+    let promoter = &disclosures.data[1];
+    assert!(promoter.is_promoter());
+    assert_eq!(promoter.side(), InsiderSide::Acquisition);
+    assert_eq!(PersonCategory::from_text("Non-Promoter"), PersonCategory::Other);
+}
+
+#[test]
+fn disclosures_work_with_the_filing_cursor() {
+    let trades: InsiderTrades = fixture("insider_trading.json");
+    let mut cursor = FilingCursor::new();
+    assert_eq!(cursor.advance(trades.data.clone()).len(), 2);
+    assert!(cursor.advance(trades.data).is_empty());
+}
+
+#[tokio::test]
+async fn disclosure_requests_carry_the_filter() {
+    let (server, scraper) = nse_serving(&[
+        ("/api/corporates-pit", "insider_trading.json"),
+        ("/api/corporate-sast-reg29", "sast_disclosures.json"),
+    ])
+    .await;
+    let filter = FilingFilter::default()
+        .symbol(Symbol::new("M&M").unwrap())
+        .date_range(date(2024, 7, 1), date(2024, 7, 5));
+
+    assert_eq!(scraper.get_insider_trades(&filter).await.unwrap().len(), 2);
+    assert_eq!(scraper.get_sast_disclosures(&FilingFilter::default()).await.unwrap().len(), 2);
+
+    let pit = &server.requests_to("/api/corporates-pit")[0];
+    assert_eq!(pit.raw_query, "index=equities&symbol=M%26M&from_date=01-07-2024&to_date=05-07-2024");
+    assert_eq!(pit.param("symbol"), Some("M&M"));
+    assert_eq!(server.requests_to("/api/corporate-sast-reg29")[0].raw_query, "index=equities");
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{fixture, nse_serving};
+use nse_scraper::models::{MostActive, MoversIndex, PriceBandHitters, Variations, YearExtremes};
+
+#[test]
+fn gainers_become_rows() {
+    let variations: Variations = fixture("variations_gainers.json");
+    let rows = variations.rows(MoversIndex::Nifty);
+    assert_eq!(rows.len(), 2);
+
+    let tata = &rows[0];
+    assert_eq!(tata.symbol, "TATAMOTORS");
+    assert_eq!(tata.last_price, 1028.9);
+    assert_eq!(tata.previous_close, Some(995.25));
+    assert_eq!(tata.p_change, Some(3.38));
+    assert!((tata.change.unwrap() - 33.65).abs() < 1e-9);
+    // Turnover is published in lakhs
+    assert_eq!(rows[1].value, Some(35512.08 * 100_000.0));
+
+    assert!(variations.rows(MoversIndex::BankNifty).is_empty());
+    assert!(variations.rows(MoversIndex::FnO).is_empty());
+}
+
+#[test]
+fn most_active_becomes_rows() {
+    let active: MostActive = fixture("most_active_volume.json");
+    let rows = active.rows();
+    assert_eq!(rows[0].symbol, "IDEA");
+    assert_eq!(rows[0].volume, Some(512345678));
+    assert_eq!(rows[0].value, Some(8912345678.9));
+    assert_eq!(rows[0].year_low, Some(10.5));
+}
+
+#[test]
+fn year_highs_carry_the_new_high() {
+    let extremes: YearExtremes = fixture("year_highs.json");
+    assert_eq!(extremes.data[0].prev_extreme_date.unwrap().to_string(), "2024-06-20");
+
+    let highs = extremes.highs();
+    assert_eq!(highs[0].year_high, Some(340.5));
+    assert_eq!(highs[0].year_low, None);
+    assert_eq!(extremes.lows()[0].year_low, Some(340.5));
+}
+
+#[test]
+fn circuit_hitters_by_side() {
+    let hitters: PriceBandHitters = fixture("price_band_hitters.json");
+
+    let upper = hitters.upper.rows();
+    assert_eq!(upper[0].symbol, "SUZLON");
+    assert!((upper[0].previous_close.unwrap() - 52.7).abs() < 1e-9);
+    assert_eq!(hitters.upper.all.as_ref().unwrap().data[0].upper_cp, Some(55.3));
+
+    let lower = &hitters.lower.all.as_ref().unwrap().data[0];
+    assert_eq!(lower.price_band, None);
+    assert_eq!(hitters.lower.rows()[0].value, Some(0.14 * 100_000.0));
+}
+
+#[tokio::test]
+async fn market_movers_request_every_list() {
+    let (server, scraper) = nse_serving(&[
+        ("/api/live-analysis-variations", "variations_gainers.json"),
+        ("/api/live-analysis-most-active-securities", "most_active_volume.json"),
+        ("/api/live-analysis-data-52weekhighstock", "year_highs.json"),
+        ("/api/live-analysis-data-52weeklowstock", "year_highs.json"),
+        ("/api/live-analysis-price-band-hitter", "price_band_hitters.json"),
+    ])
+    .await;
+
+    let movers = scraper.get_market_movers(MoversIndex::Nifty).await.unwrap();
+    assert_eq!(movers.gainers.len(), 2);
+    assert_eq!(movers.upper_circuit[0].symbol, "SUZLON");
+
+    let params = |path: &str| -> Vec<String> {
+        let mut params: Vec<String> = server.requests_to(path).iter().map(|r| r.raw_query.clone()).collect();
+        params.sort();
+        params
+    };
+    // NSE spells the losers list "loosers"
+    assert_eq!(params("/api/live-analysis-variations"), ["index=gainers", "index=loosers"]);
+    assert_eq!(params("/api/live-analysis-most-active-securities"), ["index=value", "index=volume"]);
+    assert_eq!(params("/api/live-analysis-data-52weekhighstock"), [""]);
+    assert_eq!(params("/api/live-analysis-data-52weeklowstock"), [""]);
+    assert_eq!(params("/api/live-analysis-price-band-hitter"), [""]);
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use chrono::{DateTime, FixedOffset, TimeZone};
+use common::{date, fixture};
+use nse_scraper::dates;
+use nse_scraper::models::{OptionChain, OptionChainFilter, OptionLeg};
+use nse_scraper::pricing::{self, OptionInputs, OptionType, PricingConfig};
+
+fn nifty_chain() -> OptionChain {
+    fixture("option_chain_nifty.json")
+}
+
+fn strikes(rows: &[&nse_scraper::models::OptionChainRow]) -> Vec<f64> {
+    rows.iter().map(|row| row.strike_price).collect()
+}
+
+#[test]
+fn default_filter_keeps_every_row_in_order() {
+    let chain = nifty_chain();
+    let rows = chain.rows(&OptionChainFilter::default());
+
+    assert_eq!(rows.len(), 4);
+    assert_eq!(strikes(&rows), [21900.0, 22000.0, 22100.0, 22000.0]);
+    assert_eq!(rows[3].expiry_date, date(2024, 4, 4));
+}
+
+#[test]
+fn filters_by_expiry_and_inclusive_strike_range() {
+    let chain = nifty_chain();
+
+    let near = chain.rows(&OptionChainFilter::default().expiry(date(2024, 3, 28)));
+    assert_eq!(strikes(&near), [21900.0, 22000.0, 22100.0]);
+
+    let range = OptionChainFilter::default().strike_range(22000.0, 22100.0);
+    assert_eq!(strikes(&chain.rows(&range)), [22000.0, 22100.0, 22000.0]);
+
+    let both = range.expiry(date(2024, 4, 4));
+    assert_eq!(strikes(&chain.rows(&both)), [22000.0]);
+}
+
+#[test]
+fn filter_rebuilds_expiries_and_strikes() {
+    let chain = nifty_chain();
+    let filtered = chain.filter(&OptionChainFilter::default().strike_range(21950.0, 22050.0));
+
+    assert_eq!(filtered.records.expiry_dates, [date(2024, 3, 28), date(2024, 4, 4)]);
+    assert_eq!(filtered.records.strike_prices, [22000.0]);
+    assert_eq!(filtered.records.data.len(), 2);
+    assert_eq!(filtered.underlying_value(), chain.underlying_value());
+    assert!(filtered.filtered.is_none());
+}
+
+#[test]
+fn expiry_matching_nothing_yields_an_empty_chain() {
+    let chain = nifty_chain();
+    let filter = OptionChainFilter::default().expiry(date(2024, 3, 21));
+
+    assert!(chain.rows(&filter).is_empty());
+    let filtered = chain.filter(&filter);
+    assert!(filtered.records.data.is_empty());
+    assert!(filtered.records.expiry_dates.is_empty());
+    assert!(filtered.nearest_expiry().is_none());
+    assert!(chain.around_atm(date(2024, 3, 21), 2).is_empty());
+}
+
+#[test]
+fn around_atm_centres_on_the_nearest_strike() {
+    let chain = nifty_chain();
+    let expiry = date(2024, 3, 28);
+
+    // Underlying 22023.35 sits between strikes; 22000 is nearest
+    assert_eq!(strikes(&chain.around_atm(expiry, 0)), [22000.0]);
+    assert_eq!(strikes(&chain.around_atm(expiry, 1)), [21900.0, 22000.0, 22100.0]);
+    assert_eq!(strikes(&chain.around_atm(expiry, 5)), [21900.0, 22000.0, 22100.0]);
+}
+
+#[test]
+fn around_atm_clamps_when_the_underlying_is_beyond_the_listed_strikes() {
+    let mut chain = nifty_chain();
+    chain.records.underlying_value = 23500.0;
+    let expiry = date(2024, 3, 28);
+
+    assert_eq!(strikes(&chain.around_atm(expiry, 0)), [22100.0]);
+    assert_eq!(strikes(&chain.around_atm(expiry, 1)), [22000.0, 22100.0]);
+
+    // The far expiry lists a single strike, which is the ATM one wherever the underlying is
+    assert_eq!(strikes(&chain.around_atm(date(2024, 4, 4), 2)), [22000.0]);
+}
+
+fn ist(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<FixedOffset> {
+    dates::ist().with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+/// Leg of the first row with `strike` and `expiry`
+fn leg(chain: &mut OptionChain, strike: f64, expiry: (i32, u32, u32), call: bool) -> &mut OptionLeg {
+    let expiry = date(expiry.0, expiry.1, expiry.2);
+    let row = chain.records.data
+        .iter_mut()
+        .find(|row| row.strike_price == strike && row.expiry_date == expiry)
+        .unwrap();
+    if call { row.ce.as_mut() } else { row.pe.as_mut() }.unwrap()
+}
+
+#[test]
+fn greeks_are_priced_off_the_chain_underlying() {
+    let mut chain = nifty_chain();
+    // A stale per-leg underlying must not be used
+    leg(&mut chain, 22000.0, (2024, 3, 28), true).underlying_value = 1.0;
+    let config = PricingConfig::default();
+    let now = ist(2024, 3, 15, 12, 0);
+    chain.calculate_greeks(&config, now);
+
+    let call = leg(&mut chain, 22000.0, (2024, 3, 28), true).clone();
+    let inputs = OptionInputs {
+        option_type: OptionType::Call,
+        spot: 22023.35,
+        strike: 22000.0,
+        time: pricing::time_to_expiry(now, date(2024, 3, 28), 252.0),
+        rate: config.risk_free_rate,
+        dividend_yield: config.dividend_yield,
+        trading_days_per_year: config.trading_days_per_year,
+    };
+
+    // Solved from the bid/ask mid of 115.0
+    let iv = call.computed_iv.unwrap();
+    assert!((inputs.price(iv) - 115.0).abs() < 1e-6);
+    assert_eq!(call.greeks, inputs.greeks(iv));
+    assert!(call.greeks.unwrap().delta > 0.5);
+
+    let put = leg(&mut chain, 22000.0, (2024, 3, 28), false);
+    assert!(put.computed_iv.is_some());
+    assert!(put.greeks.unwrap().delta < 0.0);
+}
+
+#[test]
+fn greeks_fill_the_filtered_view_too() {
+    let mut chain = nifty_chain();
+    chain.calculate_greeks(&PricingConfig::default(), ist(2024, 3, 15, 12, 0));
+
+    let filtered = chain.filtered.as_ref().unwrap();
+    assert!(filtered.data.iter().all(|row| row.ce.as_ref().unwrap().greeks.is_some()));
+    assert_eq!(filtered.data[1].ce.as_ref().unwrap().computed_iv, chain.records.data[1].ce.as_ref().unwrap().computed_iv);
+}
+
+#[test]
+fn untraded_legs_are_skipped() {
+    let mut chain = nifty_chain();
+    let untraded = leg(&mut chain, 22100.0, (2024, 3, 28), false);
+    untraded.last_price = 0.0;
+    untraded.bid_price = None;
+    untraded.ask_price = None;
+    chain.calculate_greeks(&PricingConfig::default(), ist(2024, 3, 15, 12, 0));
+
+    // NSE still quotes an IV of 12.2 for it, which is not used
+    let untraded = leg(&mut chain, 22100.0, (2024, 3, 28), false);
+    assert_eq!(untraded.implied_volatility, Some(12.2));
+    assert!(untraded.computed_iv.is_none());
+    assert!(untraded.greeks.is_none());
+
+    // Its call leg still trades
+    assert!(leg(&mut chain, 22100.0, (2024, 3, 28), true).greeks.is_some());
+}
+
+#[test]
+fn expired_contracts_have_no_iv_or_greeks() {
+    let mut chain = nifty_chain();
+    chain.calculate_greeks(&PricingConfig::default(), ist(2024, 3, 28, 15, 45));
+
+    for row in chain.records.data.iter().filter(|row| row.expiry_date == date(2024, 3, 28)) {
+        for leg in [&row.ce, &row.pe].into_iter().flatten() {
+            assert!(leg.computed_iv.is_none());
+            assert!(leg.greeks.is_none());
+        }
+    }
+
+    // The April contract has no NSE IV but trades, so the solver prices it
+    let april = leg(&mut chain, 22000.0, (2024, 4, 4), true);
+    assert_eq!(april.implied_volatility, None);
+    assert!(april.computed_iv.is_some());
+    assert!(april.greeks.is_some());
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{fixture, nse_serving};
+use nse_scraper::models::{PreOpenMarket, PreOpenSegment};
+
+fn market() -> PreOpenMarket {
+    fixture("pre_open_nifty.json")
+}
+
+#[test]
+fn parses_pre_open_rows() {
+    let market = market();
+    assert_eq!((market.advances, market.declines, market.unchanged), (Some(30), Some(18), Some(2)));
+    assert_eq!(market.data.len(), 3);
+
+    let reliance = &market.data[0].metadata;
+    assert_eq!(reliance.symbol, "RELIANCE");
+    assert_eq!(reliance.iep, Some(3182.5));
+    assert_eq!(reliance.change, Some(10.95));
+    assert_eq!(reliance.final_quantity, Some(48213));
+    assert_eq!(reliance.market_cap, None);
+}
+
+#[test]
+fn exposes_order_book() {
+    let market = market();
+    let reliance = &market.data[0];
+    let book = reliance.order_book();
+    assert_eq!(book.len(), 3);
+    assert!(book[1].is_iep);
+    assert_eq!(book[1].buy_qty, Some(2100));
+
+    let detail = &reliance.detail.as_ref().unwrap().pre_open_market;
+    assert_eq!(detail.ato_buy_qty, Some(1200));
+    assert_eq!(detail.last_update_time.unwrap().to_rfc3339(), "2024-07-08T09:07:58+05:30");
+    assert_eq!(reliance.buy_sell_ratio(), Some(2.0));
+
+    // No sellers, and no detail at all
+    assert_eq!(market.data[1].buy_sell_ratio(), None);
+    assert!(market.data[2].order_book().is_empty());
+}
+
+#[test]
+fn orders_by_gap() {
+    let market = market();
+    let symbols: Vec<_> = market.by_gap().iter().map(|row| row.symbol()).collect();
+    assert_eq!(symbols, ["INFY", "RELIANCE", "ITC"]);
+    assert_eq!(PreOpenSegment::FnO.as_key(), "FO");
+}
+
+#[tokio::test]
+async fn requests_the_segment_key() {
+    let (server, scraper) = nse_serving(&[("/api/market-data-pre-open", "pre_open_nifty.json")]).await;
+
+    let market = scraper.get_pre_open(PreOpenSegment::BankNifty).await.unwrap();
+    assert_eq!(market.data.len(), 3);
+
+    let request = &server.requests_to("/api/market-data-pre-open")[0];
+    assert_eq!(request.raw_query, "key=BANKNIFTY");
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use chrono::{DateTime, FixedOffset, TimeZone};
+use common::date;
+use nse_scraper::dates;
+use nse_scraper::pricing::{self, OptionInputs, OptionType};
+
+fn inputs(option_type: OptionType, strike: f64, time: f64) -> OptionInputs {
+    OptionInputs {
+        option_type,
+        spot: 100.0,
+        strike,
+        time,
+        rate: 0.05,
+        dividend_yield: 0.02,
+        trading_days_per_year: 252.0,
+    }
+}
+
+fn ist(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<FixedOffset> {
+    dates::ist().with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!((actual - expected).abs() <= tolerance, "{} is not within {} of {}", actual, tolerance, expected);
+}
+
+#[test]
+fn matches_textbook_prices() {
+    let call = OptionInputs {
+        dividend_yield: 0.0,
+        ..inputs(OptionType::Call, 100.0, 1.0)
+    };
+    let put = OptionInputs {
+        option_type: OptionType::Put,
+        ..call
+    };
+    assert_close(call.price(0.2), 10.450_583_572_185_565, 1e-12);
+    assert_close(put.price(0.2), 5.573_526_022_256_971, 1e-12);
+}
+
+#[test]
+fn implied_volatility_round_trips() {
+    let cases = [
+        (OptionType::Call, 100.0, 30.0 / 252.0),
+        (OptionType::Put, 100.0, 30.0 / 252.0),
+        // Deep in the money
+        (OptionType::Call, 65.0, 0.5),
+        (OptionType::Put, 150.0, 0.5),
+        // Deep out of the money
+        (OptionType::Call, 135.0, 0.25),
+        (OptionType::Put, 70.0, 0.25),
+    ];
+
+    for (option_type, strike, time) in cases {
+        for volatility in [0.2, 0.45, 0.9] {
+            let option = inputs(option_type, strike, time);
+            let price = option.price(volatility);
+            let implied = option.implied_volatility(price).unwrap();
+            assert_close(implied, volatility, 1e-6);
+            assert_close(option.price(implied), price, 1e-9 * price.max(1.0));
+        }
+    }
+}
+
+#[test]
+fn implied_volatility_is_none_outside_no_arbitrage_bounds() {
+    let call = inputs(OptionType::Call, 80.0, 0.5);
+    let lower_bound = 100.0 * (-0.02f64 * 0.5).exp() - 80.0 * (-0.05f64 * 0.5).exp();
+    assert!(call.implied_volatility(lower_bound - 0.5).is_none());
+    assert!(call.implied_volatility(100.5).is_none());
+    assert!(call.implied_volatility(0.0).is_none());
+    assert!(call.implied_volatility(f64::NAN).is_none());
+
+    let put = inputs(OptionType::Put, 120.0, 0.5);
+    assert!(put.implied_volatility(120.5).is_none());
+
+    let expired = inputs(OptionType::Call, 100.0, 0.0);
+    assert!(expired.implied_volatility(1.0).is_none());
+}
+
+#[test]
+fn satisfies_put_call_parity() {
+    for strike in [80.0, 100.0, 125.0] {
+        let call = inputs(OptionType::Call, strike, 0.4);
+        let put = inputs(OptionType::Put, strike, 0.4);
+        let forward = 100.0 * (-0.02f64 * 0.4).exp() - strike * (-0.05f64 * 0.4).exp();
+        assert_close(call.price(0.25) - put.price(0.25), forward, 1e-10);
+    }
+}
+
+#[test]
+fn greeks_match_finite_differences() {
+    let volatility = 0.3;
+    let h = 0.01;
+
+    for option_type in [OptionType::Call, OptionType::Put] {
+        for strike in [90.0, 100.0, 110.0] {
+            let option = inputs(option_type, strike, 0.5);
+            let greeks = option.greeks(volatility).unwrap();
+            let at_spot = |spot: f64| OptionInputs { spot, ..option }.price(volatility);
+
+            let delta = (at_spot(100.0 + h) - at_spot(100.0 - h)) / (2.0 * h);
+            let gamma = (at_spot(100.0 + h) - 2.0 * at_spot(100.0) + at_spot(100.0 - h)) / (h * h);
+            assert_close(greeks.delta, delta, 1e-6);
+            assert_close(greeks.gamma, gamma, 1e-5);
+
+            // Theta is the change over one trading day
+            let day = 1.0 / 252.0;
+            let theta = OptionInputs { time: option.time - day, ..option }.price(volatility) - option.price(volatility);
+            assert_close(greeks.theta, theta, 1e-4);
+
+            let dv = 1e-4;
+            let vega = (option.price(volatility + dv) - option.price(volatility - dv)) / (2.0 * dv) / 100.0;
+            assert_close(greeks.vega, vega, 1e-6);
+        }
+    }
+}
+
+#[test]
+fn greeks_are_none_without_time_or_volatility() {
+    assert!(inputs(OptionType::Call, 100.0, 0.0).greeks(0.2).is_none());
+    assert!(inputs(OptionType::Call, 100.0, 0.5).greeks(0.0).is_none());
+}
+
+#[test]
+fn time_to_expiry_counts_the_rest_of_the_session() {
+    let expiry = date(2024, 7, 11);
+    // Thursday 12:00: 3.5 of the 6.25 session hours are left
+    assert_close(pricing::time_to_expiry(ist(2024, 7, 11, 12, 0), expiry, 252.0), 0.56 / 252.0, 1e-12);
+    assert_close(pricing::time_to_expiry(ist(2024, 7, 11, 8, 0), expiry, 252.0), 1.0 / 252.0, 1e-12);
+    assert_eq!(pricing::time_to_expiry(ist(2024, 7, 11, 15, 30), expiry, 252.0), 0.0);
+}
+
+#[test]
+fn time_to_expiry_skips_weekends() {
+    // Friday 5 July 2024 to the Monday close
+    let expiry = date(2024, 7, 8);
+    assert_close(pricing::time_to_expiry(ist(2024, 7, 5, 12, 0), expiry, 252.0), 1.56 / 252.0, 1e-12);
+    assert_close(pricing::time_to_expiry(ist(2024, 7, 6, 10, 0), expiry, 252.0), 1.0 / 252.0, 1e-12);
+    assert_close(pricing::time_to_expiry(ist(2024, 7, 7, 23, 0), expiry, 252.0), 1.0 / 252.0, 1e-12);
+}
+
+#[test]
+fn time_to_expiry_after_the_close() {
+    let expiry = date(2024, 7, 8);
+    // After 15:30 on Friday only Monday's session is left
+    assert_close(pricing::time_to_expiry(ist(2024, 7, 5, 15, 45), expiry, 252.0), 1.0 / 252.0, 1e-12);
+    assert_eq!(pricing::time_to_expiry(ist(2024, 7, 8, 16, 0), expiry, 252.0), 0.0);
+    assert_eq!(pricing::time_to_expiry(ist(2024, 7, 9, 10, 0), expiry, 252.0), 0.0);
+}
+
+#[test]
+fn converts_other_offsets_to_ist() {
+    let expiry = date(2024, 7, 8);
+    // 06:30 UTC is 12:00 IST
+    let utc = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2024, 7, 5, 6, 30, 0).unwrap();
+    assert_close(pricing::time_to_expiry(utc, expiry, 252.0), 1.56 / 252.0, 1e-12);
+}
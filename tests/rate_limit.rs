@@ -0,0 +1,85 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{LoopbackServer, Reply};
+use nse_scraper::{NseScraper, RateLimit};
+
+const API: &str = "/api/fiidiiTradeReact";
+
+async fn nse_stub() -> LoopbackServer {
+    LoopbackServer::start(|request, _| match request.path.as_str() {
+        "/" => Reply::ok("<html></html>"),
+        API => Reply::ok("[]"),
+        _ => Reply::status(404),
+    })
+    .await
+}
+
+fn scraper(server: &LoopbackServer, limit: RateLimit) -> NseScraper {
+    NseScraper::builder()
+        .base_url(&server.url)
+        .rate_limit(limit)
+        .build()
+        .unwrap()
+}
+
+/// Gaps between consecutive requests, in arrival order
+fn gaps(server: &LoopbackServer) -> Vec<Duration> {
+    let requests = server.requests();
+    requests.windows(2).map(|pair| pair[1].received_at - pair[0].received_at).collect()
+}
+
+#[tokio::test]
+async fn spaces_requests_after_the_burst() {
+    let server = nse_stub().await;
+    let scraper = scraper(&server, RateLimit { requests_per_second: 10.0, burst: 2 });
+
+    for _ in 0..5 {
+        scraper.get_fii_dii_activity().await.unwrap();
+    }
+
+    // Priming and the first call use the burst; every later request waits for a refill
+    let gaps = gaps(&server);
+    assert_eq!(gaps.len(), 5);
+    for gap in &gaps[1..] {
+        assert!(*gap >= Duration::from_millis(90), "gaps {:?}", gaps);
+    }
+    assert!(scraper.stats().throttled >= Duration::from_millis(350));
+}
+
+#[tokio::test]
+async fn clones_share_one_bucket() {
+    let server = nse_stub().await;
+    let scraper = scraper(&server, RateLimit { requests_per_second: 20.0, burst: 1 });
+    scraper.get_fii_dii_activity().await.unwrap();
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for _ in 0..4 {
+        let scraper = scraper.clone();
+        tasks.spawn(async move { scraper.get_fii_dii_activity().await });
+    }
+    while let Some(result) = tasks.join_next().await {
+        result.unwrap().unwrap();
+    }
+
+    let requests = server.requests();
+    let span = requests.last().unwrap().received_at - requests[0].received_at;
+    assert_eq!(requests.len(), 6);
+    assert!(span >= Duration::from_millis(240), "span {:?}", span);
+}
+
+#[tokio::test]
+async fn unlimited_scraper_is_never_throttled() {
+    let server = nse_stub().await;
+    let scraper = NseScraper::builder()
+        .base_url(&server.url)
+        .without_rate_limit()
+        .build()
+        .unwrap();
+
+    for _ in 0..5 {
+        scraper.get_fii_dii_activity().await.unwrap();
+    }
+    assert_eq!(scraper.stats().throttled, Duration::ZERO);
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{LoopbackServer, Reply};
+use nse_scraper::{NseScraper, NseScraperError, RetryPolicy};
+
+const API: &str = "/api/fiidiiTradeReact";
+
+fn policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(10),
+        max_delay: Duration::from_secs(5),
+        jitter: 0.0,
+    }
+}
+
+/// Serves the home page and answers the API path with `api(hits)`
+async fn nse_stub(api: fn(usize) -> Reply) -> LoopbackServer {
+    LoopbackServer::start(move |request, hits| match request.path.as_str() {
+        "/" => Reply::ok("<html></html>").header("Set-Cookie", format!("nsit=session{}; Path=/", hits).as_str()),
+        API => api(hits),
+        _ => Reply::status(404),
+    })
+    .await
+}
+
+fn scraper(server: &LoopbackServer, policy: RetryPolicy) -> NseScraper {
+    NseScraper::builder()
+        .base_url(&server.url)
+        .retry_policy(policy)
+        .without_rate_limit()
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn reprimes_session_once_on_unauthorized() {
+    let server = nse_stub(|hits| if hits == 0 { Reply::status(401) } else { Reply::ok("[]") }).await;
+    let scraper = scraper(&server, policy(1));
+
+    scraper.get_fii_dii_activity().await.unwrap();
+
+    assert_eq!(server.requests_to("/").len(), 2);
+    let api = server.requests_to(API);
+    assert_eq!(api.len(), 2);
+    assert_eq!(api[1].header("cookie"), Some("nsit=session1"));
+    assert_eq!(scraper.stats().attempts, 2);
+}
+
+#[tokio::test]
+async fn second_rejection_surfaces_as_unauthorized() {
+    let server = nse_stub(|_| Reply::status(403)).await;
+    let scraper = scraper(&server, policy(3));
+
+    let error = scraper.get_fii_dii_activity().await.unwrap_err();
+
+    assert!(matches!(error, NseScraperError::Unauthorized { status: 403 }), "{:?}", error);
+    assert_eq!(server.requests_to("/").len(), 2);
+    assert_eq!(server.requests_to(API).len(), 2);
+    assert_eq!(scraper.stats().attempts, 2);
+}
+
+#[tokio::test]
+async fn reprime_does_not_use_up_retries() {
+    let server = nse_stub(|hits| match hits {
+        0 => Reply::status(401),
+        1 => Reply::status(503),
+        _ => Reply::ok("[]"),
+    })
+    .await;
+    let scraper = scraper(&server, policy(2));
+
+    scraper.get_fii_dii_activity().await.unwrap();
+    assert_eq!(server.requests_to(API).len(), 3);
+}
+
+#[tokio::test]
+async fn retries_a_failed_priming() {
+    let server = LoopbackServer::start(|request, hits| match (request.path.as_str(), hits) {
+        ("/", 0) => Reply::status(503),
+        ("/", _) => Reply::ok("<html></html>"),
+        _ => Reply::ok("[]"),
+    })
+    .await;
+    let scraper = scraper(&server, policy(2));
+
+    scraper.get_fii_dii_activity().await.unwrap();
+
+    assert_eq!(server.requests_to("/").len(), 2);
+    assert_eq!(server.requests_to(API).len(), 1);
+    assert_eq!(scraper.stats().attempts, 2);
+}
+
+#[tokio::test]
+async fn failed_priming_is_counted() {
+    let server = LoopbackServer::start(|_, _| Reply::status(500)).await;
+    let scraper = scraper(&server, policy(1));
+
+    let error = scraper.get_fii_dii_activity().await.unwrap_err();
+
+    assert!(matches!(error, NseScraperError::ServerError { status: 500 }), "{:?}", error);
+    assert!(server.requests_to(API).is_empty());
+    assert_eq!(scraper.stats().calls, 1);
+    assert_eq!(scraper.stats().attempts, 1);
+}
+
+#[tokio::test]
+async fn honours_retry_after() {
+    let server = nse_stub(|hits| {
+        if hits == 0 {
+            Reply::status(429).header("Retry-After", "1")
+        } else {
+            Reply::ok("[]")
+        }
+    })
+    .await;
+    let scraper = scraper(&server, policy(2));
+
+    scraper.get_fii_dii_activity().await.unwrap();
+
+    let api = server.requests_to(API);
+    let waited = api[1].received_at - api[0].received_at;
+    assert!(waited >= Duration::from_millis(950), "waited {:?}", waited);
+    assert_eq!(scraper.stats().attempts, 2);
+    assert_eq!(scraper.stats().retries(), 1);
+}
+
+#[tokio::test]
+async fn backs_off_exponentially_until_exhausted() {
+    let server = nse_stub(|_| Reply::status(500)).await;
+    let policy = RetryPolicy {
+        base_delay: Duration::from_millis(100),
+        ..policy(3)
+    };
+    let scraper = scraper(&server, policy);
+
+    let error = scraper.get_fii_dii_activity().await.unwrap_err();
+
+    match error {
+        NseScraperError::RetriesExhausted { attempts, source } => {
+            assert_eq!(attempts, 3);
+            assert!(matches!(*source, NseScraperError::ServerError { status: 500 }));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    let api = server.requests_to(API);
+    assert!(api[1].received_at - api[0].received_at >= Duration::from_millis(95));
+    assert!(api[2].received_at - api[1].received_at >= Duration::from_millis(195));
+}
+
+#[tokio::test]
+async fn does_not_retry_client_errors() {
+    let server = nse_stub(|_| Reply::status(400)).await;
+    let scraper = scraper(&server, policy(3));
+
+    let error = scraper.get_fii_dii_activity().await.unwrap_err();
+    assert!(matches!(error, NseScraperError::UnexpectedStatus { status: 400 }));
+    assert_eq!(server.requests_to(API).len(), 1);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use common::{LoopbackServer, Reply};
+use nse_scraper::{Endpoint, NseScraper};
+
+async fn nse_stub() -> LoopbackServer {
+    LoopbackServer::start(|request, _| match request.path.as_str() {
+        "/" => Reply::ok("<html></html>").header("Set-Cookie", "nsit=primed; Path=/"),
+        "/api/fiidiiTradeReact" => Reply::ok("[]"),
+        _ => Reply::status(404),
+    })
+    .await
+}
+
+#[tokio::test]
+async fn primes_session_on_base_url_override() {
+    let server = nse_stub().await;
+    let scraper = NseScraper::builder()
+        .base_url(&server.url)
+        .without_rate_limit()
+        .build()
+        .unwrap();
+
+    scraper.get_fii_dii_activity().await.unwrap();
+    scraper.get_fii_dii_activity().await.unwrap();
+
+    let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(paths, ["/", "/api/fiidiiTradeReact", "/api/fiidiiTradeReact"]);
+
+    let api = server.requests_to("/api/fiidiiTradeReact");
+    assert!(api.iter().all(|r| r.header("cookie") == Some("nsit=primed")));
+    assert!(server.requests_to("/")[0].header("accept").unwrap().starts_with("text/html"));
+}
+
+#[tokio::test]
+async fn endpoint_override_takes_precedence_over_base_url() {
+    let server = nse_stub().await;
+    let scraper = NseScraper::builder()
+        .base_url("http://127.0.0.1:9")
+        .endpoint(Endpoint::Home, &format!("{}/", server.url))
+        .endpoint(Endpoint::FiiDiiActivity, &format!("{}/api/fiidiiTradeReact", server.url))
+        .without_rate_limit()
+        .build()
+        .unwrap();
+
+    scraper.get_fii_dii_activity().await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn concurrent_rejections_reprime_once() {
+    // Set when NSE expires the session; the next home page visit renews it
+    let expired = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&expired);
+    let server = LoopbackServer::start(move |request, _| match request.path.as_str() {
+        "/" => {
+            flag.store(false, Ordering::SeqCst);
+            Reply::ok("<html></html>")
+        }
+        _ if flag.load(Ordering::SeqCst) => Reply::status(403),
+        _ => Reply::ok("[]"),
+    })
+    .await;
+    let scraper = NseScraper::builder()
+        .base_url(&server.url)
+        .without_rate_limit()
+        .build()
+        .unwrap();
+    scraper.get_fii_dii_activity().await.unwrap();
+
+    expired.store(true, Ordering::SeqCst);
+    let mut tasks = tokio::task::JoinSet::new();
+    for _ in 0..5 {
+        let scraper = scraper.clone();
+        tasks.spawn(async move { scraper.get_fii_dii_activity().await });
+    }
+    while let Some(result) = tasks.join_next().await {
+        result.unwrap().unwrap();
+    }
+
+    assert_eq!(server.requests_to("/").len(), 2);
+}
+
+#[test]
+fn rejects_invalid_base_url() {
+    let result = NseScraper::builder().base_url("not a url").build();
+    assert!(matches!(result, Err(nse_scraper::NseScraperError::ConfigError(_))));
+}
@@ -0,0 +1,15 @@
+mod common;
+
+use common::nse_serving;
+use nse_scraper::Symbol;
+
+#[tokio::test]
+async fn escapes_ampersand_in_the_query() {
+    let (server, scraper) = nse_serving(&[("/api/quote-derivative", "quote_derivative_reliance.json")]).await;
+
+    scraper.get_derivative_quote(&Symbol::new("m&m").unwrap()).await.unwrap();
+
+    let request = &server.requests_to("/api/quote-derivative")[0];
+    assert_eq!(request.raw_query, "symbol=M%26M");
+    assert_eq!(request.param("symbol"), Some("M&M"));
+}